@@ -1,6 +1,9 @@
 //! HIR for references to types. Paths in these are not yet resolved. They can
 //! be directly created from an ast::TypeRef, without further queries.
 
+#[cfg(test)]
+mod tests;
+
 use core::fmt;
 use std::fmt::Write;
 
@@ -14,12 +17,14 @@
 
 use crate::{
     builtin_type::{BuiltinInt, BuiltinType, BuiltinUint},
+    db::DefDatabase,
     hir::Literal,
     lower::LowerCtx,
     path::Path,
+    pretty::print_type_ref,
 };
 
-#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub enum Mutability {
     Shared,
     Mut,
@@ -108,7 +113,7 @@ pub(crate) fn from_ast(ctx: &LowerCtx<'_>, node: ast::Type) -> Option<Self> {
 /// Note: Most users of `TypeRef` that end up in the salsa database intern it using
 /// `Interned<TypeRef>` to save space. But notably, nested `TypeRef`s are not interned, since that
 /// does not seem to save any noticeable amount of memory.
-#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub enum TypeRef {
     Never,
     Placeholder,
@@ -132,7 +137,7 @@ pub enum TypeRef {
     Error,
 }
 
-#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub struct LifetimeRef {
     pub name: Name,
 }
@@ -151,7 +156,7 @@ pub fn missing() -> LifetimeRef {
     }
 }
 
-#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub enum TypeBound {
     Path(Path, TraitBoundModifier),
     ForLifetime(Box<[Name]>, Path),
@@ -161,7 +166,7 @@ pub enum TypeBound {
 
 /// A modifier on a bound, currently this is only used for `?Sized`, where the
 /// modifier is `Maybe`.
-#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub enum TraitBoundModifier {
     None,
     Maybe,
@@ -268,69 +273,175 @@ pub(crate) fn unit() -> TypeRef {
         TypeRef::Tuple(Vec::new())
     }
 
-    pub fn walk(&self, f: &mut impl FnMut(&TypeRef)) {
-        go(self, f);
+    /// Returns an adapter displaying this type the way it is written in source, with paths
+    /// printed like [`Path::pretty_print`] does by default.
+    pub fn display<'a>(&'a self, db: &'a dyn DefDatabase) -> impl fmt::Display + 'a {
+        struct Display<'a>(&'a dyn DefDatabase, &'a TypeRef);
+        impl fmt::Display for Display<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                print_type_ref(self.0, self.1, f)
+            }
+        }
+        Display(db, self)
+    }
 
-        fn go(type_ref: &TypeRef, f: &mut impl FnMut(&TypeRef)) {
-            f(type_ref);
-            match type_ref {
-                TypeRef::Fn(params, _, _, _) => {
-                    params.iter().for_each(|(_, param_type)| go(param_type, f))
+    /// If this is a `dyn Trait` type, returns the paths of its trait bounds. Lifetime bounds
+    /// are skipped.
+    pub fn as_dyn_trait_paths(&self) -> Option<impl Iterator<Item = &Path>> {
+        match self {
+            TypeRef::DynTrait(bounds) => {
+                Some(bounds.iter().filter_map(|bound| Some(bound.as_path()?.0)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns a copy of this type with all lifetimes erased: lifetimes of references are
+    /// elided, lifetime arguments and lifetime bounds are dropped.
+    pub fn with_lifetimes_erased(&self) -> TypeRef {
+        match self {
+            TypeRef::Path(path) => TypeRef::Path(path.with_lifetimes_erased()),
+            TypeRef::Tuple(types) => {
+                TypeRef::Tuple(types.iter().map(TypeRef::with_lifetimes_erased).collect())
+            }
+            TypeRef::RawPtr(inner, mutability) => {
+                TypeRef::RawPtr(Box::new(inner.with_lifetimes_erased()), *mutability)
+            }
+            TypeRef::Reference(inner, _, mutability) => {
+                TypeRef::Reference(Box::new(inner.with_lifetimes_erased()), None, *mutability)
+            }
+            TypeRef::Array(inner, len) => {
+                TypeRef::Array(Box::new(inner.with_lifetimes_erased()), len.clone())
+            }
+            TypeRef::Slice(inner) => TypeRef::Slice(Box::new(inner.with_lifetimes_erased())),
+            TypeRef::Fn(params, is_varargs, is_unsafe, abi) => TypeRef::Fn(
+                params
+                    .iter()
+                    .map(|(name, ty)| (name.clone(), ty.with_lifetimes_erased()))
+                    .collect(),
+                *is_varargs,
+                *is_unsafe,
+                abi.clone(),
+            ),
+            TypeRef::ImplTrait(bounds) => {
+                TypeRef::ImplTrait(bounds_with_lifetimes_erased(bounds).collect())
+            }
+            TypeRef::DynTrait(bounds) => {
+                TypeRef::DynTrait(bounds_with_lifetimes_erased(bounds).collect())
+            }
+            TypeRef::Never | TypeRef::Placeholder | TypeRef::Macro(_) | TypeRef::Error => {
+                self.clone()
+            }
+        }
+    }
+
+    pub fn walk(&self, f: &mut impl FnMut(&TypeRef)) {
+        f(self);
+        match self {
+            TypeRef::Fn(params, _, _, _) => {
+                params.iter().for_each(|(_, param_type)| param_type.walk(f))
+            }
+            TypeRef::Tuple(types) => types.iter().for_each(|t| t.walk(f)),
+            TypeRef::RawPtr(type_ref, _)
+            | TypeRef::Reference(type_ref, ..)
+            | TypeRef::Array(type_ref, _)
+            | TypeRef::Slice(type_ref) => type_ref.walk(f),
+            TypeRef::ImplTrait(bounds) | TypeRef::DynTrait(bounds) => {
+                for (path, _) in bounds.iter().filter_map(|bound| bound.as_path()) {
+                    path.walk_type_refs(f)
                 }
-                TypeRef::Tuple(types) => types.iter().for_each(|t| go(t, f)),
-                TypeRef::RawPtr(type_ref, _)
-                | TypeRef::Reference(type_ref, ..)
-                | TypeRef::Array(type_ref, _)
-                | TypeRef::Slice(type_ref) => go(type_ref, f),
-                TypeRef::ImplTrait(bounds) | TypeRef::DynTrait(bounds) => {
-                    for bound in bounds {
-                        match bound.as_ref() {
-                            TypeBound::Path(path, _) | TypeBound::ForLifetime(_, path) => {
-                                go_path(path, f)
-                            }
-                            TypeBound::Lifetime(_) | TypeBound::Error => (),
-                        }
-                    }
+            }
+            TypeRef::Path(path) => path.walk_type_refs(f),
+            TypeRef::Never | TypeRef::Placeholder | TypeRef::Macro(_) | TypeRef::Error => {}
+        }
+    }
+
+    /// Collects the paths of all types and trait bounds in this type, in the order they are
+    /// visited by [`TypeRef::walk`]. The bounds of associated type bindings, like the `Debug` in
+    /// `impl Iterator<Item: Debug>`, follow the path they appear in.
+    pub fn collect_paths(&self) -> Vec<Path> {
+        fn push_path(paths: &mut Vec<Path>, path: &Path) {
+            paths.push(path.clone());
+            let bindings = path
+                .segments()
+                .iter()
+                .filter_map(|it| it.args_and_bindings)
+                .flat_map(|it| it.bindings.iter());
+            for binding in bindings {
+                for (bound, _) in binding.bounds.iter().filter_map(|bound| bound.as_path()) {
+                    push_path(paths, bound);
                 }
-                TypeRef::Path(path) => go_path(path, f),
-                TypeRef::Never | TypeRef::Placeholder | TypeRef::Macro(_) | TypeRef::Error => {}
-            };
+            }
         }
 
-        fn go_path(path: &Path, f: &mut impl FnMut(&TypeRef)) {
-            if let Some(type_ref) = path.type_anchor() {
-                go(type_ref, f);
-            }
-            for segment in path.segments().iter() {
-                if let Some(args_and_bindings) = segment.args_and_bindings {
-                    for arg in args_and_bindings.args.iter() {
-                        match arg {
-                            crate::path::GenericArg::Type(type_ref) => {
-                                go(type_ref, f);
-                            }
-                            crate::path::GenericArg::Const(_)
-                            | crate::path::GenericArg::Lifetime(_) => {}
-                        }
-                    }
-                    for binding in args_and_bindings.bindings.iter() {
-                        if let Some(type_ref) = &binding.type_ref {
-                            go(type_ref, f);
-                        }
-                        for bound in binding.bounds.iter() {
-                            match bound.as_ref() {
-                                TypeBound::Path(path, _) | TypeBound::ForLifetime(_, path) => {
-                                    go_path(path, f)
-                                }
-                                TypeBound::Lifetime(_) | TypeBound::Error => (),
-                            }
-                        }
-                    }
+        let mut paths = Vec::new();
+        self.walk(&mut |type_ref| match type_ref {
+            TypeRef::Path(path) => push_path(&mut paths, path),
+            TypeRef::ImplTrait(bounds) | TypeRef::DynTrait(bounds) => {
+                for (path, _) in bounds.iter().filter_map(|bound| bound.as_path()) {
+                    push_path(&mut paths, path);
                 }
             }
+            _ => {}
+        });
+        paths
+    }
+
+    /// Like [`TypeRef::walk`], but allows changing the visited types. A type is visited before
+    /// its contents, so whatever `f` replaces it with is walked as well.
+    pub fn walk_mut(&mut self, f: &mut impl FnMut(&mut TypeRef)) {
+        f(self);
+        match self {
+            TypeRef::Fn(params, _, _, _) => {
+                params.iter_mut().for_each(|(_, param_type)| param_type.walk_mut(f))
+            }
+            TypeRef::Tuple(types) => types.iter_mut().for_each(|t| t.walk_mut(f)),
+            TypeRef::RawPtr(type_ref, _)
+            | TypeRef::Reference(type_ref, ..)
+            | TypeRef::Array(type_ref, _)
+            | TypeRef::Slice(type_ref) => type_ref.walk_mut(f),
+            TypeRef::ImplTrait(bounds) | TypeRef::DynTrait(bounds) => walk_bounds_mut(bounds, f),
+            TypeRef::Path(path) => path.walk_type_refs_mut(f),
+            TypeRef::Never | TypeRef::Placeholder | TypeRef::Macro(_) | TypeRef::Error => {}
         }
     }
 }
 
+/// Walks the types in the trait paths of `bounds`, see [`TypeRef::walk_mut`].
+pub(crate) fn walk_bounds_mut(
+    bounds: &mut [Interned<TypeBound>],
+    f: &mut impl FnMut(&mut TypeRef),
+) {
+    for bound in bounds {
+        if bound.as_path().is_none() {
+            continue;
+        }
+        let mut new_bound = TypeBound::clone(bound);
+        if let TypeBound::Path(path, _) | TypeBound::ForLifetime(_, path) = &mut new_bound {
+            path.walk_type_refs_mut(f);
+        }
+        *bound = Interned::new(new_bound);
+    }
+}
+
+pub(crate) fn bounds_with_lifetimes_erased(
+    bounds: &[Interned<TypeBound>],
+) -> impl Iterator<Item = Interned<TypeBound>> + '_ {
+    bounds.iter().filter_map(|bound| {
+        let bound = match &**bound {
+            TypeBound::Path(path, modifier) => {
+                TypeBound::Path(path.with_lifetimes_erased(), modifier.clone())
+            }
+            TypeBound::ForLifetime(lifetimes, path) => {
+                TypeBound::ForLifetime(lifetimes.clone(), path.with_lifetimes_erased())
+            }
+            TypeBound::Lifetime(_) => return None,
+            TypeBound::Error => TypeBound::Error,
+        };
+        Some(Interned::new(bound))
+    })
+}
+
 pub(crate) fn type_bounds_from_ast(
     lower_ctx: &LowerCtx<'_>,
     type_bounds_opt: Option<ast::TypeBoundList>,
@@ -388,7 +499,7 @@ pub fn as_path(&self) -> Option<(&Path, &TraitBoundModifier)> {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ConstRef {
     Scalar(LiteralConstRef),
     Path(Name),
@@ -399,7 +510,7 @@ impl ConstRef {
     pub(crate) fn from_const_arg(lower_ctx: &LowerCtx<'_>, arg: Option<ast::ConstArg>) -> Self {
         if let Some(arg) = arg {
             if let Some(expr) = arg.expr() {
-                return Self::from_expr(expr, Some(lower_ctx.ast_id(&arg)));
+                return Self::from_expr(expr, || lower_ctx.ast_id(&arg));
             }
         }
         Self::Scalar(LiteralConstRef::Unknown)
@@ -426,22 +537,9 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Display(db, self)
     }
 
-    // We special case literals and single identifiers, to speed up things.
-    fn from_expr(expr: ast::Expr, ast_id: Option<AstId<ast::ConstArg>>) -> Self {
-        fn is_path_ident(p: &ast::PathExpr) -> bool {
-            let Some(path) = p.path() else {
-                return false;
-            };
-            if path.coloncolon_token().is_some() {
-                return false;
-            }
-            if let Some(s) = path.segment() {
-                if s.coloncolon_token().is_some() || s.generic_arg_list().is_some() {
-                    return false;
-                }
-            }
-            true
-        }
+    // We special case literals and single identifiers, to speed up things. Only the remaining
+    // expressions need an `AstId`, so lowering the former also works for detached nodes.
+    fn from_expr(expr: ast::Expr, ast_id: impl FnOnce() -> AstId<ast::ConstArg>) -> Self {
         match expr {
             ast::Expr::PathExpr(p) if is_path_ident(&p) => {
                 match p.path().and_then(|it| it.segment()).and_then(|it| it.name_ref()) {
@@ -459,19 +557,38 @@ fn is_path_ident(p: &ast::PathExpr) -> bool {
                 ast::LiteralKind::Bool(f) => LiteralConstRef::Bool(f),
                 _ => LiteralConstRef::Unknown,
             }),
-            _ => {
-                if let Some(ast_id) = ast_id {
-                    Self::Complex(ast_id)
-                } else {
-                    Self::Scalar(LiteralConstRef::Unknown)
-                }
-            }
+            _ => Self::Complex(ast_id()),
+        }
+    }
+
+    /// Whether lowering the const arg `expr` needs its `AstId`, i.e. whether it is neither a
+    /// literal nor a single identifier.
+    pub(crate) fn needs_ast_id(expr: &ast::Expr) -> bool {
+        match expr {
+            ast::Expr::PathExpr(p) => !is_path_ident(p),
+            ast::Expr::Literal(_) => false,
+            _ => true,
+        }
+    }
+}
+
+fn is_path_ident(p: &ast::PathExpr) -> bool {
+    let Some(path) = p.path() else {
+        return false;
+    };
+    if path.coloncolon_token().is_some() {
+        return false;
+    }
+    if let Some(s) = path.segment() {
+        if s.coloncolon_token().is_some() || s.generic_arg_list().is_some() {
+            return false;
         }
     }
+    true
 }
 
 /// A literal constant value
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum LiteralConstRef {
     Int(i128),
     UInt(u128),
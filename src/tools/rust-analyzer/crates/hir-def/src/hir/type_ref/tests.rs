@@ -0,0 +1,87 @@
+use std::fmt::Write;
+
+use base_db::SourceDatabase;
+use expect_test::{expect, Expect};
+use syntax::ast::{self, AstNode};
+use test_fixture::WithFixture;
+
+use crate::{lower::LowerCtx, path::Path, test_db::TestDB, type_ref::TypeRef};
+
+/// Lowers the type in `type T = {ty};`.
+fn lower_type(ty: &str) -> (TestDB, TypeRef) {
+    let (db, file_id) = TestDB::with_single_file(&format!("type T = {ty};"));
+    let file = db.parse(file_id).tree();
+    let ast_ty = file.syntax().descendants().find_map(ast::TypeAlias::cast).unwrap();
+    let ctx = LowerCtx::new(&db, file_id.into());
+    let type_ref = TypeRef::from_ast(&ctx, ast_ty.ty().unwrap());
+    (db, type_ref)
+}
+
+fn print_paths(db: &TestDB, paths: &[Path]) -> String {
+    let mut buf = String::new();
+    for path in paths {
+        writeln!(buf, "{}", path.display_def(db)).unwrap();
+    }
+    buf
+}
+
+fn check_display(ty: &str, expect: Expect) {
+    let (db, type_ref) = lower_type(ty);
+    expect.assert_eq(&type_ref.display(&db).to_string());
+}
+
+#[test]
+fn display() {
+    check_display("(u8, &'a mut [T; 4])", expect![[r#"(u8, &'a mut [T; 4])"#]]);
+    check_display(
+        r#"unsafe extern "C" fn(i32, ...) -> *const u8"#,
+        expect![[r#"unsafe extern "C" fn(i32, ...) -> *const u8"#]],
+    );
+    check_display(r#"extern "C" fn(args: ...)"#, expect![[r#"extern "C" fn(...) -> ()"#]]);
+    check_display("&dyn Send", expect![[r#"&dyn Send"#]]);
+    check_display("impl Iterator<Item = u8> + 'a", expect![[r#"impl Iterator<Item = u8> + 'a"#]]);
+}
+
+#[test]
+fn collect_paths() {
+    let (db, type_ref) = lower_type("(Vec<u8>, &(dyn Iterator<Item = Foo> + Send))");
+    let paths = type_ref.collect_paths();
+    expect![[r#"
+        Vec<u8>
+        u8
+        Iterator<Item = Foo>
+        Send
+        Foo
+    "#]]
+    .assert_eq(&print_paths(&db, &paths));
+
+    let (db, type_ref) = lower_type("impl Iterator<Item: Debug + Into<Foo<Bar: Baz>>>");
+    let paths = type_ref.collect_paths();
+    expect![[r#"
+        Iterator<Item: Debug + Into<Foo<Bar: Baz>>>
+        Debug
+        Into<Foo<Bar: Baz>>
+        Foo<Bar: Baz>
+        Baz
+    "#]]
+    .assert_eq(&print_paths(&db, &paths));
+}
+
+#[test]
+fn parenthesized_types() {
+    let (_, type_ref) = lower_type("(i32)");
+    assert!(matches!(type_ref, TypeRef::Path(_)));
+
+    let (db, type_ref) = lower_type("&(dyn A + Send)");
+    let TypeRef::Reference(pointee, ..) = &type_ref else { panic!("expected a reference") };
+    let TypeRef::DynTrait(bounds) = &**pointee else { panic!("expected a trait object") };
+    assert_eq!(bounds.len(), 2);
+    assert_eq!(type_ref.display(&db).to_string(), "&(dyn A + Send)");
+
+    // The parser lets the bounds of `dyn` extend over the `+`, so the unparenthesized form, which
+    // rustc rejects as ambiguous, lowers to the same type as the one rustc suggests instead.
+    let (_, unparenthesized) = lower_type("&dyn A + Send");
+    assert_eq!(unparenthesized, type_ref);
+
+    check_display("*const (dyn A)", expect![[r#"*const dyn A"#]]);
+}
@@ -9,13 +9,14 @@
 use itertools::Itertools;
 use rustc_hash::{FxHashSet, FxHasher};
 use smallvec::SmallVec;
-use stdx::{format_to, TupleExt};
+use stdx::{format_to, never, TupleExt};
 use triomphe::Arc;
 
 use crate::{
     db::DefDatabase,
     item_scope::{ImportOrExternCrate, ItemInNs},
     nameres::DefMap,
+    path::{ModPath, PathKind},
     visibility::Visibility,
     AssocItemId, ModuleDefId, ModuleId, TraitId,
 };
@@ -33,6 +34,32 @@ pub struct ImportInfo {
     pub is_unstable: bool,
 }
 
+impl ImportInfo {
+    /// Returns the path through which the item is exported, relative to the root of the crate
+    /// this info belongs to, e.g. `foo::Bar` for a `Bar` re-exported by the public module `foo`.
+    pub fn path(&self, db: &dyn DefDatabase) -> Option<ModPath> {
+        let mut module = self.container;
+        let mut segments = vec![self.name.clone()];
+
+        let def_map = module.def_map(db);
+        never!(def_map.block_id().is_some(), "block local items should not be in `ImportMap`");
+
+        while let Some(parent) = module.containing_module(db) {
+            let parent_data = &def_map[parent.local_id];
+            let Some((name, _)) =
+                parent_data.children.iter().find(|(_, id)| **id == module.local_id)
+            else {
+                never!("module {:?} is not a child of its parent {:?}", module, parent);
+                return None;
+            };
+            segments.push(name.clone());
+            module = parent;
+        }
+
+        Some(ModPath::from_segments(PathKind::Plain, segments.into_iter().rev()))
+    }
+}
+
 /// A map from publicly exported items to its name.
 ///
 /// Reexports of items are taken into account.
@@ -125,6 +152,12 @@ pub fn import_info_for(&self, item: ItemInNs) -> Option<&[ImportInfo]> {
         self.item_to_info_map.get(&item).map(|(info, _)| &**info)
     }
 
+    /// Returns all items exported by the crate, including items that are only reachable through
+    /// `pub use`, along with every public path they can be imported through.
+    pub fn exported_items(&self) -> impl Iterator<Item = (ItemInNs, &[ImportInfo])> + '_ {
+        self.item_to_info_map.iter().map(|(&item, (info, _))| (item, &**info))
+    }
+
     fn collect_import_map(db: &dyn DefDatabase, krate: CrateId) -> ImportMapIndex {
         let _p = tracing::span!(tracing::Level::INFO, "collect_import_map").entered();
 
@@ -484,9 +517,8 @@ mod tests {
     impl ImportMap {
         fn fmt_for_test(&self, db: &dyn DefDatabase) -> String {
             let mut importable_paths: Vec<_> = self
-                .item_to_info_map
-                .iter()
-                .flat_map(|(item, (info, _))| info.iter().map(move |info| (item, info)))
+                .exported_items()
+                .flat_map(|(item, info)| info.iter().map(move |info| (item, info)))
                 .map(|(item, info)| {
                     let path = render_path(db, info);
                     let ns = match item {
@@ -602,21 +634,7 @@ fn check(ra_fixture: &str, expect: Expect) {
     }
 
     fn render_path(db: &dyn DefDatabase, info: &ImportInfo) -> String {
-        let mut module = info.container;
-        let mut segments = vec![&info.name];
-
-        let def_map = module.def_map(db);
-        assert!(def_map.block_id().is_none(), "block local items should not be in `ImportMap`");
-
-        while let Some(parent) = module.containing_module(db) {
-            let parent_data = &def_map[parent.local_id];
-            let (name, _) =
-                parent_data.children.iter().find(|(_, id)| **id == module.local_id).unwrap();
-            segments.push(name);
-            module = parent;
-        }
-
-        segments.iter().rev().map(|it| it.display(db.upcast())).join("::")
+        info.path(db).unwrap().display(db.upcast()).to_string()
     }
 
     #[test]
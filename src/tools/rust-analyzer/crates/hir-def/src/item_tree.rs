@@ -51,7 +51,7 @@
 use smallvec::SmallVec;
 use span::{AstIdNode, FileAstId, SyntaxContextId};
 use stdx::never;
-use syntax::{ast, match_ast, SyntaxKind};
+use syntax::{ast, match_ast, AstPtr, SyntaxKind};
 use triomphe::Arc;
 
 use crate::{
@@ -843,6 +843,48 @@ pub fn use_tree_source_map(
         .expect("failed to lower use tree")
         .1
     }
+
+    /// Expands this import like [`UseTree::imports`], pairing every import with a pointer to the
+    /// `UseTree` it comes from.
+    pub fn imports_with_source(
+        &self,
+        db: &dyn DefDatabase,
+        file_id: HirFileId,
+    ) -> Vec<(LoweredImport, InFile<AstPtr<ast::UseTree>>)> {
+        let source_map = self.use_tree_source_map(db, file_id);
+        self.use_tree
+            .imports()
+            .into_iter()
+            .map(|import| {
+                let ptr = InFile::new(file_id, AstPtr::new(&source_map[import.index]));
+                (import, ptr)
+            })
+            .collect()
+    }
+}
+
+/// Why a use tree was dropped during lowering, see [`use_tree_errors`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UseTreeError {
+    /// The path of the tree can't be lowered, like the `bar::` in `use foo::{bar::};`.
+    InvalidPath,
+    /// The tree has no path, like in `use foo::{as bar};`.
+    MissingPath,
+    /// A glob import is renamed, like in `use foo::* as bar;`.
+    RenamedGlob,
+    /// A top-level `self` import, like `use self;`. Unlike the others, such a tree is still
+    /// lowered, but it never resolves.
+    SelfImport,
+}
+
+/// Returns the malformed parts of the use tree `tree` in the file `file_id`, which don't show up
+/// in the lowered [`UseTree`].
+pub fn use_tree_errors(
+    db: &dyn DefDatabase,
+    file_id: HirFileId,
+    tree: ast::UseTree,
+) -> Vec<(ast::UseTree, UseTreeError)> {
+    lower::use_tree_errors(db, tree, &mut |range| db.span_map(file_id).span_for_range(range).ctx)
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -855,8 +897,35 @@ pub enum ImportKind {
     TypeOnly,
 }
 
+/// A single import of a [`UseTree`], see [`UseTree::imports`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoweredImport {
+    /// The index of the tree this import comes from, see [`Use::use_tree_to_ast`].
+    pub index: Idx<ast::UseTree>,
+    pub path: ModPath,
+    pub kind: ImportKind,
+    pub alias: Option<ImportAlias>,
+}
+
 impl UseTree {
+    /// Returns the structure of this tree, with nested trees kept grouped under their prefix.
+    pub fn kind(&self) -> &UseTreeKind {
+        &self.kind
+    }
+
+    /// Expands the `UseTree` into individually imported `ModPath`s, like [`UseTree::expand`].
+    pub fn imports(&self) -> Vec<LoweredImport> {
+        let mut imports = Vec::new();
+        self.expand(|index, path, kind, alias| {
+            imports.push(LoweredImport { index, path, kind, alias })
+        });
+        imports
+    }
+
     /// Expands the `UseTree` into individually imported `ModPath`s.
+    ///
+    /// Every expanded path owns a copy of its full prefix; only the grouped [`UseTree`] itself
+    /// shares it between the nested imports.
     pub fn expand(
         &self,
         mut cb: impl FnMut(Idx<ast::UseTree>, ModPath, ImportKind, Option<ImportAlias>),
@@ -866,34 +935,40 @@ pub fn expand(
 
     fn expand_impl(
         &self,
-        prefix: Option<ModPath>,
+        prefix: Option<&ModPath>,
         cb: &mut impl FnMut(Idx<ast::UseTree>, ModPath, ImportKind, Option<ImportAlias>),
     ) {
+        // The prefix is only borrowed while walking the tree. Each expanded path still copies the
+        // prefix segments, but it is built with a single allocation instead of cloning the prefix
+        // once per nesting level.
         fn concat_mod_paths(
-            prefix: Option<ModPath>,
+            prefix: Option<&ModPath>,
             path: &ModPath,
         ) -> Option<(ModPath, ImportKind)> {
+            let concat = |kind, prefix: &ModPath| {
+                ModPath::from_segments(
+                    kind,
+                    prefix.segments().iter().chain(path.segments()).cloned(),
+                )
+            };
             match (prefix, path.kind) {
                 (None, _) => Some((path.clone(), ImportKind::Plain)),
-                (Some(mut prefix), PathKind::Plain) => {
-                    prefix.extend(path.segments().iter().cloned());
-                    Some((prefix, ImportKind::Plain))
+                (Some(prefix), PathKind::Plain) => {
+                    Some((concat(prefix.kind, prefix), ImportKind::Plain))
                 }
-                (Some(mut prefix), PathKind::Super(n)) if n > 0 && prefix.segments().is_empty() => {
+                (Some(prefix), PathKind::Super(n)) if n > 0 && prefix.segments().is_empty() => {
                     // `super::super` + `super::rest`
-                    match &mut prefix.kind {
+                    match prefix.kind {
                         PathKind::Super(m) => {
                             cov_mark::hit!(concat_super_mod_paths);
-                            *m += n;
-                            prefix.extend(path.segments().iter().cloned());
-                            Some((prefix, ImportKind::Plain))
+                            Some((concat(PathKind::Super(m + n), prefix), ImportKind::Plain))
                         }
                         _ => None,
                     }
                 }
                 (Some(prefix), PathKind::Super(0)) if path.segments().is_empty() => {
                     // `some::path::self` == `some::path`
-                    Some((prefix, ImportKind::TypeOnly))
+                    Some((prefix.clone(), ImportKind::TypeOnly))
                 }
                 (Some(_), _) => None,
             }
@@ -912,19 +987,23 @@ fn concat_mod_paths(
             }
             UseTreeKind::Glob { path: None } => {
                 if let Some(prefix) = prefix {
-                    cb(self.index, prefix, ImportKind::Glob, None);
+                    cb(self.index, prefix.clone(), ImportKind::Glob, None);
                 }
             }
             UseTreeKind::Prefixed { prefix: additional_prefix, list } => {
+                let concatenated;
                 let prefix = match additional_prefix {
                     Some(path) => match concat_mod_paths(prefix, path) {
-                        Some((path, ImportKind::Plain)) => Some(path),
+                        Some((path, ImportKind::Plain)) => {
+                            concatenated = path;
+                            Some(&concatenated)
+                        }
                         _ => return,
                     },
                     None => prefix,
                 };
                 for tree in &**list {
-                    tree.expand_impl(prefix.clone(), cb);
+                    tree.expand_impl(prefix, cb);
                 }
             }
         }
@@ -18,11 +18,10 @@
         AssocItem, AttrOwner, Const, Either, Enum, ExternBlock, ExternCrate, Field, FieldAstId,
         Fields, FileItemTreeId, FnFlags, Function, GenericArgs, Idx, IdxRange, Impl, ImportAlias,
         Interned, ItemTree, ItemTreeData, ItemTreeNode, Macro2, MacroCall, MacroRules, Mod,
-        ModItem, ModKind, ModPath, Mutability, Name, Param, ParamAstId, Path, Range, RawAttrs,
-        RawIdx, RawVisibilityId, Static, Struct, StructKind, Trait, TraitAlias, TypeAlias, Union,
-        Use, UseTree, UseTreeKind, Variant,
+        ModItem, ModKind, ModPath, Mutability, Name, Param, ParamAstId, Path, PathKind, Range,
+        RawAttrs, RawIdx, RawVisibilityId, Static, Struct, StructKind, Trait, TraitAlias,
+        TypeAlias, Union, Use, UseTree, UseTreeError, UseTreeKind, Variant,
     },
-    path::AssociatedTypeBinding,
     type_ref::{LifetimeRef, TraitBoundModifier, TraitRef, TypeBound, TypeRef},
     visibility::RawVisibility,
     LocalLifetimeParamId, LocalTypeOrConstParamId,
@@ -717,16 +716,7 @@ fn desugar_future_path(orig: TypeRef) -> Path {
     let path = path![core::future::Future];
     let mut generic_args: Vec<_> =
         std::iter::repeat(None).take(path.segments().len() - 1).collect();
-    let binding = AssociatedTypeBinding {
-        name: name![Output],
-        args: None,
-        type_ref: Some(orig),
-        bounds: Box::default(),
-    };
-    generic_args.push(Some(Interned::new(GenericArgs {
-        bindings: Box::new([binding]),
-        ..GenericArgs::empty()
-    })));
+    generic_args.push(Some(Interned::new(GenericArgs::empty().with_binding(name![Output], orig))));
 
     Path::from_known_path(path, generic_args)
 }
@@ -748,6 +738,7 @@ fn lower_abi(abi: ast::Abi) -> Interned<str> {
 struct UseTreeLowering<'a> {
     db: &'a dyn DefDatabase,
     mapping: Arena<ast::UseTree>,
+    errors: Vec<(ast::UseTree, UseTreeError)>,
 }
 
 impl UseTreeLowering<'_> {
@@ -758,16 +749,18 @@ fn lower_use_tree(
     ) -> Option<UseTree> {
         if let Some(use_tree_list) = tree.use_tree_list() {
             let prefix = match tree.path() {
+                // E.g. `use ::{inner}`, which makes the inner paths absolute
+                None if tree.coloncolon_token().is_some() => {
+                    Some(ModPath::from_kind(PathKind::Abs))
+                }
                 // E.g. use something::{{{inner}}};
                 None => None,
                 // E.g. `use something::{inner}` (prefix is `None`, path is `something`)
                 // or `use something::{path::{inner::{innerer}}}` (prefix is `something::path`, path is `inner`)
-                Some(path) => {
-                    match ModPath::from_src(self.db.upcast(), path, span_for_range) {
-                        Some(it) => Some(it),
-                        None => return None, // FIXME: report errors somewhere
-                    }
-                }
+                Some(path) => match self.lower_path(path, span_for_range) {
+                    Some(it) => Some(it),
+                    None => return self.error(tree, UseTreeError::InvalidPath),
+                },
             };
 
             let list = use_tree_list
@@ -784,27 +777,28 @@ fn lower_use_tree(
         } else {
             let is_glob = tree.star_token().is_some();
             let path = match tree.path() {
-                Some(path) => Some(ModPath::from_src(self.db.upcast(), path, span_for_range)?),
+                Some(path) => match self.lower_path(path, span_for_range) {
+                    Some(it) => Some(it),
+                    None => return self.error(tree, UseTreeError::InvalidPath),
+                },
                 None => None,
             };
             let alias = tree.rename().map(|a| {
                 a.name().map(|it| it.as_name()).map_or(ImportAlias::Underscore, ImportAlias::Alias)
             });
             if alias.is_some() && is_glob {
-                return None;
+                return self.error(tree, UseTreeError::RenamedGlob);
             }
 
             match (path, alias, is_glob) {
-                (path, None, true) => {
+                (path, _, true) => {
                     if path.is_none() {
                         cov_mark::hit!(glob_enum_group);
                     }
                     Some(self.use_tree(UseTreeKind::Glob { path: path.map(Interned::new) }, tree))
                 }
-                // Globs can't be renamed
-                (_, Some(_), true) | (None, None, false) => None,
                 // `bla::{ as Name}` is invalid
-                (None, Some(_), false) => None,
+                (None, _, false) => self.error(tree, UseTreeError::MissingPath),
                 (Some(path), alias, false) => Some(
                     self.use_tree(UseTreeKind::Single { path: Interned::new(path), alias }, tree),
                 ),
@@ -812,10 +806,26 @@ fn lower_use_tree(
         }
     }
 
+    fn lower_path(
+        &self,
+        path: ast::Path,
+        span_for_range: &mut dyn FnMut(::tt::TextRange) -> SyntaxContextId,
+    ) -> Option<ModPath> {
+        // `ModPath::from_src` skips a missing last segment, like the one of `bar::` in
+        // `use foo::{bar::};`
+        path.segment()?;
+        ModPath::from_src(self.db.upcast(), path, span_for_range)
+    }
+
     fn use_tree(&mut self, kind: UseTreeKind, ast: ast::UseTree) -> UseTree {
         let index = self.mapping.alloc(ast);
         UseTree { index, kind }
     }
+
+    fn error(&mut self, ast: ast::UseTree, error: UseTreeError) -> Option<UseTree> {
+        self.errors.push((ast, error));
+        None
+    }
 }
 
 pub(crate) fn lower_use_tree(
@@ -823,7 +833,29 @@ pub(crate) fn lower_use_tree(
     tree: ast::UseTree,
     span_for_range: &mut dyn FnMut(::tt::TextRange) -> SyntaxContextId,
 ) -> Option<(UseTree, Arena<ast::UseTree>)> {
-    let mut lowering = UseTreeLowering { db, mapping: Arena::new() };
+    let mut lowering = UseTreeLowering { db, mapping: Arena::new(), errors: Vec::new() };
     let tree = lowering.lower_use_tree(tree, span_for_range)?;
     Some((tree, lowering.mapping))
 }
+
+/// Lowers `tree` only to collect the subtrees that were dropped because they are malformed.
+pub(crate) fn use_tree_errors(
+    db: &dyn DefDatabase,
+    tree: ast::UseTree,
+    span_for_range: &mut dyn FnMut(::tt::TextRange) -> SyntaxContextId,
+) -> Vec<(ast::UseTree, UseTreeError)> {
+    let mut lowering = UseTreeLowering { db, mapping: Arena::new(), errors: Vec::new() };
+    let is_self_import = tree.use_tree_list().is_none()
+        && tree.path().is_some_and(|path| {
+            path.qualifier().is_none()
+                && matches!(
+                    path.segment().and_then(|it| it.kind()),
+                    Some(ast::PathSegmentKind::SelfKw)
+                )
+        });
+    if is_self_import {
+        lowering.errors.push((tree.clone(), UseTreeError::SelfImport));
+    }
+    lowering.lower_use_tree(tree, span_for_range);
+    lowering.errors
+}
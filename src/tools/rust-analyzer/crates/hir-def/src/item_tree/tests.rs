@@ -1,7 +1,13 @@
+use base_db::SourceDatabase;
 use expect_test::{expect, Expect};
+use syntax::ast::{self, AstNode};
 use test_fixture::WithFixture;
 
-use crate::{db::DefDatabase, test_db::TestDB};
+use crate::{
+    db::DefDatabase,
+    item_tree::{use_tree_errors, ImportKind, ModItem, UseTreeError, UseTreeKind},
+    test_db::TestDB,
+};
 
 fn check(ra_fixture: &str, expect: Expect) {
     let (db, file_id) = TestDB::with_single_file(ra_fixture);
@@ -56,6 +62,95 @@ fn imports() {
     );
 }
 
+#[test]
+fn use_tree_structure() {
+    let (db, file_id) = TestDB::with_single_file("use a::{b, c::{d as e, *}, self};");
+    let item_tree = db.file_item_tree(file_id.into());
+    let ModItem::Use(id) = item_tree.top_level_items()[0] else { panic!("expected an import") };
+    let UseTreeKind::Prefixed { prefix: Some(prefix), list } = item_tree[id].use_tree.kind() else {
+        panic!("expected a prefixed use tree")
+    };
+    assert_eq!(prefix.segments().iter().map(|it| it.as_str()).collect::<Vec<_>>(), [Some("a")]);
+    assert_eq!(list.len(), 3);
+    assert!(matches!(list[0].kind(), UseTreeKind::Single { alias: None, .. }));
+    assert!(matches!(
+        list[1].kind(),
+        UseTreeKind::Prefixed { prefix: Some(_), list } if list.len() == 2
+    ));
+    assert!(matches!(list[2].kind(), UseTreeKind::Single { alias: None, .. }));
+}
+
+#[test]
+fn use_tree_imports() {
+    let (db, file_id) = TestDB::with_single_file("use a::{b as c, d::*, e::{self}};");
+    let item_tree = db.file_item_tree(file_id.into());
+    let ModItem::Use(id) = item_tree.top_level_items()[0] else { panic!("expected an import") };
+    let imports = item_tree[id].use_tree.imports();
+    let imports = imports
+        .iter()
+        .map(|import| {
+            let path = import.path.segments().iter().map(|it| it.as_str().unwrap()).collect();
+            let alias = import.alias.as_ref().map(|it| it.to_string());
+            (path, import.kind, alias)
+        })
+        .collect::<Vec<(Vec<_>, _, _)>>();
+    assert_eq!(
+        imports,
+        [
+            (vec!["a", "b"], ImportKind::Plain, Some("c".to_owned())),
+            (vec!["a", "d"], ImportKind::Glob, None),
+            (vec!["a", "e"], ImportKind::TypeOnly, None),
+        ]
+    );
+}
+
+#[test]
+fn use_tree_imports_with_source() {
+    let (db, file_id) = TestDB::with_single_file("use a::{b as c, d::{*, self}};");
+    let item_tree = db.file_item_tree(file_id.into());
+    let ModItem::Use(id) = item_tree.top_level_items()[0] else { panic!("expected an import") };
+    let root = db.parse(file_id).syntax_node();
+    let sources = item_tree[id]
+        .imports_with_source(&db, file_id.into())
+        .into_iter()
+        .map(|(import, ptr)| (import.kind, ptr.value.to_node(&root).syntax().to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sources,
+        [
+            (ImportKind::Plain, "b as c".to_owned()),
+            (ImportKind::Glob, "*".to_owned()),
+            (ImportKind::TypeOnly, "self".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn malformed_use_trees() {
+    let (db, file_id) = TestDB::with_single_file(
+        r#"
+use foo::{bar::, baz};
+use self;
+use foo::{self, bar};
+"#,
+    );
+    let file = db.parse(file_id).tree();
+    let errors = file
+        .syntax()
+        .descendants()
+        .filter_map(ast::Use::cast)
+        .flat_map(|it| use_tree_errors(&db, file_id.into(), it.use_tree().unwrap()))
+        .map(|(tree, error)| (tree.syntax().to_string(), error))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        [
+            ("bar::,".to_owned(), UseTreeError::InvalidPath),
+            ("self".to_owned(), UseTreeError::SelfImport)
+        ]
+    );
+}
+
 #[test]
 fn extern_blocks() {
     check(
@@ -332,13 +427,13 @@ struct S {
             // AstId: 1
             pub(self) struct S {
                 // AstId: 2
-                pub(self) a: Mixed::<'a, T, Item = (), OtherItem = u8>,
+                pub(self) a: Mixed<'a, T, Item = (), OtherItem = u8>,
                 // AstId: 3
-                pub(self) b: Qualified::<Self=Fully>::Syntax,
+                pub(self) b: Qualified<Self=Fully>::Syntax,
                 // AstId: 4
                 pub(self) c: <TypeAnchored>::Path::<'a>,
                 // AstId: 5
-                pub(self) d: dyn for<'a> Trait::<'a>,
+                pub(self) d: dyn for<'a> Trait<'a>,
             }
         "#]],
     )
@@ -387,7 +482,7 @@ pub(self) struct Tuple<T, U>(
                 U: ?Sized;
 
             // AstId: 3
-            impl<'a, 'b, T, const K: u8> S::<'a, 'b, T, K>
+            impl<'a, 'b, T, const K: u8> S<'a, 'b, T, K>
             where
                 T: Copy,
                 T: 'a,
@@ -415,7 +510,7 @@ pub(self) trait Tr<'a, Self, T>
             where
                 Self: Super,
                 T: 'a,
-                Self: for<'a> Tr::<'a, T>
+                Self: for<'a> Tr<'a, T>
             {
             }
         "#]],
@@ -12,7 +12,7 @@
     FunctionId, ImplId, ModuleDefId, StaticId, StructId, TraitId, TypeAliasId, UnionId,
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum LangItemTarget {
     EnumId(EnumId),
     Function(FunctionId),
@@ -76,6 +76,22 @@ pub fn as_enum_variant(self) -> Option<EnumVariantId> {
     }
 }
 
+impl From<LangItemTarget> for AttrDefId {
+    fn from(target: LangItemTarget) -> Self {
+        match target {
+            LangItemTarget::EnumId(it) => AttrDefId::AdtId(it.into()),
+            LangItemTarget::Function(it) => it.into(),
+            LangItemTarget::ImplDef(it) => it.into(),
+            LangItemTarget::Static(it) => it.into(),
+            LangItemTarget::Struct(it) => AttrDefId::AdtId(it.into()),
+            LangItemTarget::Union(it) => AttrDefId::AdtId(it.into()),
+            LangItemTarget::TypeAlias(it) => it.into(),
+            LangItemTarget::Trait(it) => it.into(),
+            LangItemTarget::EnumVariant(it) => it.into(),
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct LangItems {
     items: FxHashMap<LangItem, LangItemTarget>,
@@ -77,7 +77,7 @@
     item_scope::{BuiltinShadowMode, ItemScope},
     item_tree::{ItemTreeId, Mod, TreeId},
     nameres::{diagnostics::DefDiagnostic, path_resolution::ResolveMode},
-    path::ModPath,
+    path::{ModPath, Path},
     per_ns::PerNs,
     visibility::{Visibility, VisibilityExplicitness},
     AstId, BlockId, BlockLoc, CrateRootModuleId, EnumId, EnumVariantId, ExternCrateId, FunctionId,
@@ -607,6 +607,22 @@ pub(crate) fn resolve_path(
         (res.resolved_def, res.segment_index)
     }
 
+    /// Resolves `path` in `module`, returning the definitions per namespace and, if only a prefix
+    /// of the path resolved, the index of the first unresolved segment. Returns `None` for paths
+    /// that can't be resolved with the def map alone, i.e. type-anchored and lang item paths.
+    pub fn resolve_hir_path(
+        &self,
+        db: &dyn DefDatabase,
+        module: LocalModuleId,
+        path: &Path,
+    ) -> Option<(PerNs, Option<usize>)> {
+        if path.type_anchor().is_some() {
+            return None;
+        }
+        let path = path.mod_path()?;
+        Some(self.resolve_path(db, module, path, BuiltinShadowMode::Other, None))
+    }
+
     pub(crate) fn resolve_path_locally(
         &self,
         db: &dyn DefDatabase,
@@ -6,10 +6,14 @@
 
 use base_db::SourceDatabase;
 use expect_test::{expect, Expect};
+use syntax::ast::{self, AstNode};
 use test_fixture::WithFixture;
 use triomphe::Arc;
 
-use crate::{db::DefDatabase, nameres::DefMap, test_db::TestDB};
+use crate::{
+    db::DefDatabase, lower::LowerCtx, nameres::DefMap, path::Path, test_db::TestDB, AdtId,
+    ModuleDefId,
+};
 
 fn compute_crate_def_map(ra_fixture: &str) -> Arc<DefMap> {
     let db = TestDB::with_files(ra_fixture);
@@ -311,6 +315,64 @@ fn edition_2015_imports() {
     );
 }
 
+#[test]
+fn braced_use_without_prefix() {
+    check(
+        r#"
+//- /main.rs crate:main deps:foo edition:2018
+mod local {
+    pub struct Local;
+}
+use ::{foo::FromLib};
+use {local::Local};
+
+//- /lib.rs crate:foo
+pub struct FromLib;
+"#,
+        expect![[r#"
+            crate
+            FromLib: ti vi
+            Local: ti vi
+            local: t
+
+            crate::local
+            Local: t v
+        "#]],
+    );
+}
+
+#[test]
+fn braced_use_without_prefix_2015() {
+    check(
+        r#"
+//- /main.rs crate:main edition:2015
+mod foo;
+mod bar;
+
+//- /bar.rs
+pub struct Bar;
+pub struct Baz;
+
+//- /foo.rs
+use ::{bar::Bar};
+use {bar::Baz};
+"#,
+        expect![[r#"
+            crate
+            bar: t
+            foo: t
+
+            crate::bar
+            Bar: t v
+            Baz: t v
+
+            crate::foo
+            Bar: ti vi
+            Baz: ti vi
+        "#]],
+    );
+}
+
 #[test]
 fn item_map_using_self() {
     check(
@@ -339,6 +401,37 @@ fn item_map_using_self() {
     );
 }
 
+#[test]
+fn item_map_using_nested_self_with_alias() {
+    check(
+        r#"
+//- /lib.rs
+mod foo {
+    pub mod bar {
+        pub struct Baz;
+    }
+}
+use foo::bar::{self as b, Baz};
+use foo::{{bar::{{self as c}}}, self as f};
+use self::foo::{bar::{self as _}};
+"#,
+        expect![[r#"
+            crate
+            Baz: ti vi
+            b: ti
+            c: ti
+            f: ti
+            foo: t
+
+            crate::foo
+            bar: t
+
+            crate::foo::bar
+            Baz: t v
+        "#]],
+    );
+}
+
 #[test]
 fn item_map_across_crates() {
     check(
@@ -929,3 +1022,45 @@ mod some_submodule {
         "#]],
     )
 }
+
+#[test]
+fn resolve_hir_path_in_def_map() {
+    let (db, file_id) = TestDB::with_single_file(
+        r#"
+mod foo {
+    pub struct Bar;
+}
+type A = foo::Bar<u8>;
+type B = foo::Bar::new;
+type C = foo::Baz;
+type D = <u8>::foo;
+"#,
+    );
+    let file = db.parse(file_id).tree();
+    let ctx = LowerCtx::new(&db, file_id.into());
+    let paths = file
+        .syntax()
+        .descendants()
+        .filter_map(ast::TypeAlias::cast)
+        .map(|it| {
+            let Some(ast::Type::PathType(ty)) = it.ty() else { panic!("expected a path type") };
+            Path::from_src(&ctx, ty.path().unwrap()).unwrap()
+        })
+        .collect::<Vec<_>>();
+    let krate = db.crate_graph().iter().next().unwrap();
+    let def_map = db.crate_def_map(krate);
+    let resolve = |path: &Path| def_map.resolve_hir_path(&db, DefMap::ROOT, path);
+
+    let (res, unresolved) = resolve(&paths[0]).unwrap();
+    assert!(matches!(res.take_types(), Some(ModuleDefId::AdtId(AdtId::StructId(_)))));
+    assert_eq!(unresolved, None);
+
+    let (res, unresolved) = resolve(&paths[1]).unwrap();
+    assert!(matches!(res.take_types(), Some(ModuleDefId::AdtId(AdtId::StructId(_)))));
+    assert_eq!(unresolved, Some(2));
+
+    let (res, _) = resolve(&paths[2]).unwrap();
+    assert!(res.is_none());
+
+    assert!(resolve(&paths[3]).is_none());
+}
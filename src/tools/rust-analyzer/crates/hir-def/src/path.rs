@@ -1,22 +1,39 @@
 //! A desugared representation of paths like `crate::foo` or `<Type as Trait>::bar`.
+mod builder;
 mod lower;
+mod parse;
+pub mod rewrite;
+#[cfg(test)]
+mod tests;
+mod use_item;
 
 use std::{
     fmt::{self, Display},
+    hash::{Hash, Hasher},
     iter,
 };
 
 use crate::{
+    db::DefDatabase,
     lang_item::LangItemTarget,
     lower::LowerCtx,
-    type_ref::{ConstRef, LifetimeRef, TypeBound, TypeRef},
+    nameres::DefMap,
+    type_ref::{
+        bounds_with_lifetimes_erased, walk_bounds_mut, ConstRef, LifetimeRef, TypeBound, TypeRef,
+    },
+    GenericParamId, ModuleId,
 };
-use hir_expand::name::Name;
+use base_db::CrateId;
+use hir_expand::name::{name, AsName, Name};
 use intern::Interned;
-use syntax::ast;
+use rustc_hash::{FxHashMap, FxHasher};
+use span::Edition;
+use syntax::ast::{self, AstNode};
 
 pub use hir_expand::mod_path::{path, ModPath, PathKind};
 
+pub use self::{builder::PathBuilder, parse::PathParseError};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ImportAlias {
     /// Unnamed alias, as in `use Foo as _;`
@@ -34,7 +51,9 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Paths are ordered structurally: normal paths by their type anchor, then by their [`ModPath`]
+/// (kind, then segment names), then by their generic args, followed by all lang item paths.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Path {
     /// A normal path
     Normal {
@@ -50,26 +69,127 @@ pub enum Path {
     LangItem(LangItemTarget, Option<Name>),
 }
 
+/// One of the crates shipped with the standard library, see [`Path::std_crate_of`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StdCrate {
+    Std,
+    Core,
+    Alloc,
+}
+
+impl StdCrate {
+    /// Returns the crate that known paths like `std::ops::Try` should be rooted at in crates
+    /// with the def map `def_map`: `core` in `#![no_std]` crates, `std` otherwise.
+    pub fn for_def_map(def_map: &DefMap) -> StdCrate {
+        if def_map.is_no_std() {
+            StdCrate::Core
+        } else {
+            StdCrate::Std
+        }
+    }
+
+    pub fn name(self) -> Name {
+        match self {
+            StdCrate::Std => name![std],
+            StdCrate::Core => name![core],
+            StdCrate::Alloc => name![alloc],
+        }
+    }
+}
+
+/// Options for [`Path::pretty_print`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyPrintOpts {
+    /// Whether to print the generic args of the path's segments.
+    pub show_generic_args: bool,
+    /// Whether to drop a leading `std` segment, printing `std::vec::Vec` as `vec::Vec`.
+    pub use_short_std: bool,
+    /// Whether to print the leading `::` of absolute paths.
+    pub show_leading_separator: bool,
+    /// Whether to print `$crate` as the name of the crate it refers to.
+    pub resolve_dollar_crate: bool,
+    /// Whether to print paths desugared from `<Type as Trait>::foo` in that form instead of as
+    /// `Trait::<Self=Type>::foo`.
+    pub resugar_qualified_paths: bool,
+}
+
+impl Default for PrettyPrintOpts {
+    fn default() -> Self {
+        PrettyPrintOpts {
+            show_generic_args: true,
+            use_short_std: false,
+            show_leading_separator: true,
+            resolve_dollar_crate: false,
+            resugar_qualified_paths: false,
+        }
+    }
+}
+
+/// How [`Path::display_with`] prints the crate at the start of absolute and `$crate` paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CratePrefix {
+    /// Print absolute paths with their leading `::` and `$crate` as the display name of the crate
+    /// it refers to, like [`Path::display_def`].
+    Unchanged,
+    /// Print crates by the name the given crate knows them as: `crate` for the crate itself and
+    /// the (possibly renamed) dependency name for others, without a leading `::`.
+    RelativeTo(CrateId),
+}
+
+/// Displays a [`Path`] the way users would write it, see [`Path::display_def`].
+pub struct PathDisplay<'a> {
+    path: &'a Path,
+    db: &'a dyn DefDatabase,
+    prefix: CratePrefix,
+}
+
+impl fmt::Display for PathDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let opts = PrettyPrintOpts {
+            resolve_dollar_crate: true,
+            resugar_qualified_paths: true,
+            ..PrettyPrintOpts::default()
+        };
+        match self.prefix {
+            CratePrefix::Unchanged => self.path.pretty_print(self.db, f, opts),
+            CratePrefix::RelativeTo(krate) => {
+                let opts = PrettyPrintOpts { show_leading_separator: false, ..opts };
+                match self.path.resolve_dollar_crate(self.db, krate) {
+                    Some(path) => path.pretty_print(self.db, f, opts),
+                    // The crate can't be named from `krate`, so keep the `$crate`.
+                    None => self.path.pretty_print(
+                        self.db,
+                        f,
+                        PrettyPrintOpts { resolve_dollar_crate: false, ..opts },
+                    ),
+                }
+            }
+        }
+    }
+}
+
 /// Generic arguments to a path segment (e.g. the `i32` in `Option<i32>`). This
 /// also includes bindings of associated types, like in `Iterator<Item = Foo>`.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct GenericArgs {
     pub args: Box<[GenericArg]>,
-    /// This specifies whether the args contain a Self type as the first
-    /// element. This is the case for path segments like `<T as Trait>`, where
-    /// `T` is actually a type parameter for the path `Trait` specifying the
-    /// Self type. Otherwise, when we have a path `Trait<X, Y>`, the Self type
-    /// is left out.
-    pub has_self_type: bool,
+    /// The Self type of path segments like `<T as Trait>`, where `T` is
+    /// actually a type parameter for the path `Trait` specifying the Self
+    /// type. Otherwise, when we have a path `Trait<X, Y>`, the Self type is
+    /// left out. It is not part of `args`.
+    pub self_type: Option<TypeRef>,
     /// Associated type bindings like in `Iterator<Item = T>`.
     pub bindings: Box<[AssociatedTypeBinding]>,
     /// Whether these generic args were desugared from `Trait(Arg) -> Output`
     /// parenthesis notation typically used for the `Fn` traits.
     pub desugared_from_fn: bool,
+    /// Whether these generic args were written with turbofish syntax, as in `Vec::<u8>::new` or
+    /// `collect::<Vec<_>>()`. Only these are printed with a leading `::`.
+    pub is_turbofish: bool,
 }
 
 /// An associated type binding like in `Iterator<Item = T>`.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct AssociatedTypeBinding {
     /// The name of the associated type.
     pub name: Name,
@@ -86,17 +206,43 @@ pub struct AssociatedTypeBinding {
 }
 
 /// A single generic argument.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum GenericArg {
     Type(TypeRef),
     Lifetime(LifetimeRef),
     Const(ConstRef),
 }
 
+/// The reason lowering an `ast::Path` failed, see [`Path::try_from_src`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathLoweringError {
+    /// A segment has no name, like the last one in `foo::`.
+    MissingSegment,
+    /// A qualified path has no self type, like `<>::foo`.
+    MissingSelfType,
+    /// The trait of a qualified path like `<T as Trait>::foo` is missing or can't be lowered.
+    InvalidQualifiedTrait,
+}
+
+impl fmt::Display for PathLoweringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PathLoweringError::MissingSegment => "missing path segment",
+            PathLoweringError::MissingSelfType => "missing self type in qualified path",
+            PathLoweringError::InvalidQualifiedTrait => "invalid trait in qualified path",
+        })
+    }
+}
+
 impl Path {
     /// Converts an `ast::Path` to `Path`. Works with use trees.
     /// It correctly handles `$crate` based path from macro call.
     pub fn from_src(ctx: &LowerCtx<'_>, path: ast::Path) -> Option<Path> {
+        Path::try_from_src(ctx, path).ok()
+    }
+
+    /// Like [`Path::from_src`], but returns why lowering failed.
+    pub fn try_from_src(ctx: &LowerCtx<'_>, path: ast::Path) -> Result<Path, PathLoweringError> {
         lower::lower_path(ctx, path)
     }
 
@@ -119,6 +265,39 @@ pub fn from_known_path_with_no_generic(path: ModPath) -> Path {
         Path::Normal { type_anchor: None, mod_path: Interned::new(path), generic_args: None }
     }
 
+    /// Creates an absolute path into the crate `crate_name`, see
+    /// [`ModPath::from_fully_qualified`].
+    pub fn from_fully_qualified(
+        crate_name: &Name,
+        segments: impl IntoIterator<Item = Name>,
+    ) -> Path {
+        Path::from_known_path_with_no_generic(ModPath::from_fully_qualified(crate_name, segments))
+    }
+
+    /// Creates an absolute path into `std`, e.g. `::std::collections::HashMap`.
+    pub fn from_fully_qualified_in_std(segments: impl IntoIterator<Item = Name>) -> Path {
+        Path::from_fully_qualified(&name![std], segments)
+    }
+
+    /// Creates an absolute path into `core`, e.g. `::core::option::Option`.
+    pub fn from_fully_qualified_in_core(segments: impl IntoIterator<Item = Name>) -> Path {
+        Path::from_fully_qualified(&name![core], segments)
+    }
+
+    /// A placeholder path for places where lowering failed but a path is needed to continue.
+    /// `name` is usually [`Name::missing`], but can be whatever part of the name could be
+    /// recovered.
+    pub fn for_error_recovery(name: Name) -> Path {
+        Path::from(name)
+    }
+
+    /// Returns whether this is a placeholder created by [`Path::for_error_recovery`] with
+    /// [`Name::missing`]. Placeholders with a recovered name can't be told apart from a path
+    /// consisting of just that name.
+    pub fn is_error_recovery(&self) -> bool {
+        self.mod_path().and_then(ModPath::as_ident).is_some_and(Name::is_missing)
+    }
+
     pub fn kind(&self) -> &PathKind {
         match self {
             Path::Normal { mod_path, .. } => &mod_path.kind,
@@ -152,6 +331,12 @@ pub fn segments(&self) -> PathSegments<'_> {
         }
     }
 
+    /// Splits the segments of this path into the last one and the ones qualifying it, without
+    /// cloning like [`Path::qualifier`] does.
+    pub fn split_last(&self) -> Option<(PathSegments<'_>, PathSegment<'_>)> {
+        self.segments().split_last()
+    }
+
     pub fn mod_path(&self) -> Option<&ModPath> {
         match self {
             Path::Normal { mod_path, .. } => Some(mod_path),
@@ -177,6 +362,630 @@ pub fn qualifier(&self) -> Option<Path> {
         Some(res)
     }
 
+    /// Returns an iterator over this path and its qualifiers, e.g. `a::b::c`, `a::b` and `a`.
+    /// Paths without segments, like `self`, only yield themselves.
+    pub fn ancestors(&self) -> impl Iterator<Item = Path> {
+        iter::successors(Some(self.clone()), |path| {
+            if path.segments().len() > 1 {
+                path.qualifier()
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns an adapter displaying this path for use in diagnostics. Unlike the output of
+    /// [`Path::pretty_print`] with default options, `$crate` is replaced with the name of the
+    /// crate it refers to and qualified paths are printed as `<Type as Trait>::foo`.
+    pub fn display_def<'a>(&'a self, db: &'a dyn DefDatabase) -> PathDisplay<'a> {
+        self.display_with(db, CratePrefix::Unchanged)
+    }
+
+    /// Like [`Path::display_def`], but prints the crate at the start of absolute and `$crate` paths
+    /// according to `prefix`.
+    pub fn display_with<'a>(
+        &'a self,
+        db: &'a dyn DefDatabase,
+        prefix: CratePrefix,
+    ) -> PathDisplay<'a> {
+        PathDisplay { path: self, db, prefix }
+    }
+
+    /// Synthesizes an `ast::Path` for this path, written like [`Path::display_def`] does. Returns
+    /// `None` for paths that can't be written in source, like lang item paths or paths
+    /// containing unknown types.
+    pub fn to_ast(&self, db: &dyn DefDatabase) -> Option<ast::Path> {
+        if let Path::LangItem(..) = self {
+            return None;
+        }
+        let text = self.display_def(db).to_string();
+        let parse = ast::SourceFile::parse(&format!("type T = {text};"));
+        if !parse.errors().is_empty() {
+            return None;
+        }
+        let path = match parse.tree().syntax().descendants().find_map(ast::Type::cast)? {
+            ast::Type::PathType(it) => it.path()?,
+            _ => return None,
+        };
+        (path.syntax().text() == text.as_str()).then(|| path.clone_subtree())
+    }
+
+    /// Prints this path into `out`, with `opts` controlling which parts of it are shown.
+    pub fn pretty_print(
+        &self,
+        db: &dyn DefDatabase,
+        out: &mut dyn fmt::Write,
+        opts: PrettyPrintOpts,
+    ) -> fmt::Result {
+        crate::pretty::print_path_with_opts(db, self, out, opts)
+    }
+
+    /// If this path is relative to the crate root (`crate::foo`), returns it without the
+    /// `crate::` prefix.
+    pub fn try_strip_crate_prefix(&self) -> Option<Path> {
+        self.try_strip_kind(PathKind::Crate)
+    }
+
+    /// If this is an absolute path (`::foo`), returns it without the leading `::`.
+    pub fn try_strip_abs_prefix(&self) -> Option<Path> {
+        self.try_strip_kind(PathKind::Abs)
+    }
+
+    /// Returns whether the first segment of this path is resolved in the crate root, `in_import`
+    /// telling whether the path is the path of a `use` item. This is the case for `crate::foo`
+    /// and, in the 2015 edition, for `::foo` and for plain `foo::Bar` imports.
+    ///
+    /// Note that in the 2015 edition those paths fall back to the extern prelude if `foo` isn't
+    /// found in the crate root.
+    pub fn is_crate_relative(&self, edition: Edition, in_import: bool) -> bool {
+        let Some(mod_path) = self.mod_path() else { return false };
+        match mod_path.kind {
+            PathKind::Crate => true,
+            PathKind::Abs => edition == Edition::Edition2015,
+            PathKind::Plain => {
+                edition == Edition::Edition2015 && in_import && !mod_path.segments().is_empty()
+            }
+            PathKind::Super(_) | PathKind::DollarCrate(_) => false,
+        }
+    }
+
+    /// Returns whether the first segment of this path may name a crate from the extern prelude,
+    /// which starting with the 2018 edition is the case for `::foo` and plain `foo::Bar`. A
+    /// plain path only does so if `foo` isn't found in scope first, while `::foo` can only name
+    /// a crate.
+    pub fn is_extern_prelude_candidate(&self, edition: Edition) -> bool {
+        match self.mod_path() {
+            Some(mod_path) if matches!(mod_path.kind, PathKind::Abs | PathKind::Plain) => {
+                edition >= Edition::Edition2018 && !mod_path.segments().is_empty()
+            }
+            _ => false,
+        }
+    }
+
+    fn try_strip_kind(&self, kind: PathKind) -> Option<Path> {
+        match self {
+            Path::Normal { type_anchor, mod_path, generic_args } if mod_path.kind == kind => {
+                Some(Path::Normal {
+                    type_anchor: type_anchor.clone(),
+                    mod_path: Interned::new(ModPath::from_segments(
+                        PathKind::Plain,
+                        mod_path.segments().iter().cloned(),
+                    )),
+                    generic_args: generic_args.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Appends the segments of `other` to this path, e.g. `foo::bar` joined with `baz::Qux<T>`
+    /// becomes `foo::bar::baz::Qux<T>`. Returns `None` if `other` isn't a plain relative path
+    /// (like `::foo`, `self::foo`, `crate::foo` or `<T>::foo`) or if this is a lang item path.
+    pub fn join(self, other: Path) -> Option<Path> {
+        let (
+            Path::Normal { type_anchor, mod_path, generic_args },
+            Path::Normal { type_anchor: None, mod_path: other_mod_path, generic_args: other_args },
+        ) = (self, other)
+        else {
+            return None;
+        };
+        if other_mod_path.kind != PathKind::Plain {
+            return None;
+        }
+
+        let generic_args = match (generic_args, other_args) {
+            (None, None) => None,
+            (generic_args, other_args) => {
+                let args = |args: Option<Box<[_]>>, len| match args {
+                    Some(it) => it.into_vec(),
+                    None => vec![None; len],
+                };
+                let mut generic_args = args(generic_args, mod_path.segments().len());
+                generic_args.extend(args(other_args, other_mod_path.segments().len()));
+                Some(generic_args.into_boxed_slice())
+            }
+        };
+        Some(Path::Normal {
+            type_anchor,
+            mod_path: Interned::new(ModPath::from_segments(
+                mod_path.kind,
+                mod_path.segments().iter().chain(other_mod_path.segments()).cloned(),
+            )),
+            generic_args,
+        })
+    }
+
+    /// Appends a segment without generic args to this path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is a lang item path that already has a segment.
+    pub fn push_segment(&mut self, name: Name) {
+        match self {
+            Path::Normal { mod_path, generic_args, .. } => {
+                let mut new_mod_path = ModPath::clone(mod_path);
+                new_mod_path.push_segment(name);
+                *mod_path = Interned::new(new_mod_path);
+                if let Some(generic_args) = generic_args {
+                    let mut args = std::mem::take(generic_args).into_vec();
+                    args.push(None);
+                    *generic_args = args.into_boxed_slice();
+                }
+            }
+            Path::LangItem(_, segment @ None) => *segment = Some(name),
+            Path::LangItem(_, Some(_)) => panic!("lang item paths can only have one segment"),
+        }
+    }
+
+    /// Removes the last segment of this path and returns its name, dropping its generic args.
+    pub fn pop_segment(&mut self) -> Option<Name> {
+        match self {
+            Path::Normal { mod_path, generic_args, .. } => {
+                let mut new_mod_path = ModPath::clone(mod_path);
+                let name = new_mod_path.pop_segment()?;
+                *mod_path = Interned::new(new_mod_path);
+                if let Some(args) = generic_args.take() {
+                    let mut args = args.into_vec();
+                    args.pop();
+                    *generic_args = args.iter().any(Option::is_some).then(|| args.into());
+                }
+                Some(name)
+            }
+            Path::LangItem(_, segment) => segment.take(),
+        }
+    }
+
+    /// Replaces the segment at `idx` in place, see [`Path::replace_segment`] for a version
+    /// returning a modified copy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no segment at `idx` or if this is a lang item path.
+    pub fn set_segment(&mut self, idx: usize, segment: PathSegment<'_>) {
+        *self = self.replace_segment(idx, |_| segment).expect("no path segment at index");
+    }
+
+    /// Changes the kind of this path, keeping its segments.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is a lang item path or a path with a type anchor, which is always plain.
+    pub fn set_kind(&mut self, kind: PathKind) {
+        match self {
+            Path::Normal { type_anchor: None, mod_path, .. } => {
+                *mod_path = Interned::new(ModPath::from_segments(
+                    kind,
+                    mod_path.segments().iter().cloned(),
+                ));
+            }
+            Path::Normal { type_anchor: Some(_), .. } if kind == PathKind::Plain => {}
+            Path::Normal { type_anchor: Some(_), .. } => {
+                panic!("paths with a type anchor can't have a kind")
+            }
+            Path::LangItem(..) => panic!("lang item paths can't have a kind"),
+        }
+    }
+
+    /// Calls `f` on every type nested in this path, in its type anchor as well as its generic
+    /// args, see [`TypeRef::walk`].
+    pub fn walk_type_refs(&self, f: &mut impl FnMut(&TypeRef)) {
+        if let Some(type_ref) = self.type_anchor() {
+            type_ref.walk(f);
+        }
+        for args in self.segments().iter().filter_map(|it| it.args_and_bindings) {
+            args.walk_type_refs(f);
+        }
+    }
+
+    /// Like [`Path::walk_type_refs`], but allows changing the visited types, see
+    /// [`TypeRef::walk_mut`].
+    pub fn walk_type_refs_mut(&mut self, f: &mut impl FnMut(&mut TypeRef)) {
+        let Path::Normal { type_anchor, generic_args, .. } = self else {
+            return;
+        };
+        if let Some(type_anchor) = type_anchor {
+            let mut type_ref = TypeRef::clone(type_anchor);
+            type_ref.walk_mut(f);
+            *type_anchor = Interned::new(type_ref);
+        }
+        for args in generic_args.iter_mut().flat_map(|it| it.iter_mut()).flatten() {
+            let mut new_args = GenericArgs::clone(args);
+            new_args.walk_type_refs_mut(f);
+            *args = Interned::new(new_args);
+        }
+    }
+
+    /// Rewrites a `self::` or `super::` path appearing in `module` into the equivalent `crate::`
+    /// path, e.g. `super::foo` in `crate::a::b` becomes `crate::a::foo`. Other paths are returned
+    /// unchanged. Returns `None` if the path climbs above the crate root or if `module` is inside
+    /// a block, whose items can't be named from the crate root.
+    pub fn canonicalize_in(&self, db: &dyn DefDatabase, module: ModuleId) -> Option<Path> {
+        let Path::Normal { type_anchor: None, mod_path, generic_args } = self else {
+            return Some(self.clone());
+        };
+        let PathKind::Super(levels) = mod_path.kind else {
+            return Some(self.clone());
+        };
+        if module.containing_block().is_some() {
+            return None;
+        }
+
+        let mut module_names = Vec::new();
+        let mut current = module;
+        while let Some(parent) = current.containing_module(db) {
+            module_names.push(current.name(db)?);
+            current = parent;
+        }
+        module_names.reverse();
+        module_names.truncate(module_names.len().checked_sub(levels as usize)?);
+
+        let generic_args = generic_args.as_ref().map(|generic_args| {
+            iter::repeat(None)
+                .take(module_names.len())
+                .chain(generic_args.iter().cloned())
+                .collect()
+        });
+        Some(Path::Normal {
+            type_anchor: None,
+            mod_path: Interned::new(ModPath::from_segments(
+                PathKind::Crate,
+                module_names.into_iter().chain(mod_path.segments().iter().cloned()),
+            )),
+            generic_args,
+        })
+    }
+
+    /// Replaces a leading `$crate` with a path to the root of the crate it refers to, as seen from
+    /// `local_crate`: `crate::` if it is `local_crate` itself, `::dep::` if `local_crate` depends
+    /// on it as `dep`. Returns `None` if the crate can't be named from `local_crate`, like the
+    /// defining crate of a macro re-exported by a dependency. Other paths are returned unchanged.
+    pub fn resolve_dollar_crate(&self, db: &dyn DefDatabase, local_crate: CrateId) -> Option<Path> {
+        let Path::Normal { type_anchor: None, mod_path, generic_args } = self else {
+            return Some(self.clone());
+        };
+        let PathKind::DollarCrate(krate) = mod_path.kind else {
+            return Some(self.clone());
+        };
+        if krate == local_crate {
+            let mut path = self.clone();
+            path.set_kind(PathKind::Crate);
+            return Some(path);
+        }
+
+        let crate_graph = db.crate_graph();
+        let crate_name = crate_graph[local_crate]
+            .dependencies
+            .iter()
+            .find(|dep| dep.crate_id == krate)
+            .map(|dep| dep.as_name())?;
+
+        let generic_args = generic_args
+            .as_ref()
+            .map(|generic_args| iter::once(None).chain(generic_args.iter().cloned()).collect());
+        Some(Path::Normal {
+            type_anchor: None,
+            mod_path: Interned::new(ModPath::from_segments(
+                PathKind::Abs,
+                iter::once(crate_name).chain(mod_path.segments().iter().cloned()),
+            )),
+            generic_args,
+        })
+    }
+
+    /// Returns whether this path and `other` have the same kind and segment names, ignoring
+    /// generic args and type anchors.
+    pub fn eq_ignoring_args(&self, other: &Path) -> bool {
+        match (self, other) {
+            (Path::Normal { mod_path, .. }, Path::Normal { mod_path: other, .. }) => {
+                mod_path == other
+            }
+            (Path::LangItem(..), Path::LangItem(..)) => self == other,
+            _ => false,
+        }
+    }
+
+    /// Returns a hash of this path that is the same across sessions and processes, so it can key
+    /// caches living outside of the database. Unlike the derived `Hash`, which hashes interned
+    /// pointers, this feeds [`FxHasher`] a fixed encoding of length-prefixed text fields packed
+    /// into little-endian `u64` words: the path kind, the type anchor and each segment's name and
+    /// generic args, the latter two printed as by [`Path::display_def`]. Lang item paths hash the
+    /// lang item's name and their segment. The value only changes with that encoding or the
+    /// pinned `rustc-hash` version, and like `FxHasher` itself depends on the pointer width.
+    /// `$crate` paths hash the same whatever crate they refer to.
+    pub fn stable_hash(&self, db: &dyn DefDatabase) -> u64 {
+        let mut hasher = FxHasher::default();
+        let mut write_field = |text: &str| {
+            hasher.write_u64(text.len() as u64);
+            for chunk in text.as_bytes().chunks(8) {
+                let mut word = [0; 8];
+                word[..chunk.len()].copy_from_slice(chunk);
+                hasher.write_u64(u64::from_le_bytes(word));
+            }
+        };
+        match self {
+            Path::Normal { type_anchor, mod_path, .. } => {
+                let kind = match mod_path.kind {
+                    PathKind::Plain => "plain".to_owned(),
+                    PathKind::Super(n) => format!("super{n}"),
+                    PathKind::Crate => "crate".to_owned(),
+                    PathKind::Abs => "abs".to_owned(),
+                    PathKind::DollarCrate(_) => "$crate".to_owned(),
+                };
+                write_field(&kind);
+                let mut anchor = String::new();
+                if let Some(type_anchor) = type_anchor {
+                    let _ = crate::pretty::print_type_ref(db, type_anchor, &mut anchor);
+                }
+                write_field(&anchor);
+                for segment in self.segments().iter() {
+                    write_field(&segment.name.display(db.upcast()).to_string());
+                    let mut args = String::new();
+                    if let Some(generic_args) = segment.args_and_bindings {
+                        let _ = crate::pretty::print_generic_args(db, generic_args, &mut args);
+                    }
+                    write_field(&args);
+                }
+            }
+            Path::LangItem(target, segment) => {
+                write_field("lang");
+                let lang_item = db.lang_attr((*target).into());
+                write_field(lang_item.map(|it| it.name()).as_deref().unwrap_or_default());
+                let segment = segment.as_ref().map(|it| it.display(db.upcast()).to_string());
+                write_field(segment.as_deref().unwrap_or_default());
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Hashes this path consistently with [`Path::eq_ignoring_args`].
+    pub fn hash_ignoring_args<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Path::Normal { mod_path, .. } => mod_path.hash(state),
+            Path::LangItem(..) => self.hash(state),
+        }
+    }
+
+    /// Returns whether `prefix` has the same kind and type anchor as this path and its segment
+    /// names are a prefix of this path's. Generic args are ignored.
+    pub fn starts_with(&self, prefix: &Path) -> bool {
+        let (
+            Path::Normal { type_anchor, mod_path, .. },
+            Path::Normal { type_anchor: prefix_anchor, mod_path: prefix_mod_path, .. },
+        ) = (self, prefix)
+        else {
+            return false;
+        };
+        type_anchor == prefix_anchor
+            && mod_path.kind == prefix_mod_path.kind
+            && mod_path.segments().starts_with(prefix_mod_path.segments())
+    }
+
+    /// Returns the segments following `prefix` as a plain path, keeping their generic args. See
+    /// [`Path::starts_with`] for how the prefix is matched. Returns `None` if this path doesn't
+    /// start with `prefix` or if no segments remain.
+    pub fn strip_prefix(&self, prefix: &Path) -> Option<Path> {
+        if !self.starts_with(prefix) {
+            return None;
+        }
+        let Path::Normal { mod_path, generic_args, .. } = self else {
+            return None;
+        };
+        let len = prefix.segments().len();
+        if len == mod_path.segments().len() {
+            return None;
+        }
+        let generic_args = generic_args
+            .as_ref()
+            .map(|it| &it[len..])
+            .filter(|it| it.iter().any(Option::is_some))
+            .map(Into::into);
+        Some(Path::Normal {
+            type_anchor: None,
+            mod_path: Interned::new(ModPath::from_segments(
+                PathKind::Plain,
+                mod_path.segments()[len..].iter().cloned(),
+            )),
+            generic_args,
+        })
+    }
+
+    /// Returns a copy of this path with all lifetimes erased from its generic args, see
+    /// [`GenericArgs::with_lifetimes_erased`].
+    pub fn with_lifetimes_erased(&self) -> Path {
+        let Path::Normal { type_anchor, mod_path, generic_args } = self else {
+            return self.clone();
+        };
+        let generic_args = generic_args.as_ref().and_then(|generic_args| {
+            let generic_args: Box<[_]> = generic_args
+                .iter()
+                .map(|args| {
+                    let args = args.as_ref()?.with_lifetimes_erased();
+                    (args.self_type.is_some() || !args.args.is_empty() || !args.bindings.is_empty())
+                        .then(|| Interned::new(args))
+                })
+                .collect();
+            generic_args.iter().any(Option::is_some).then_some(generic_args)
+        });
+        Path::Normal {
+            type_anchor: type_anchor.as_ref().map(|it| Interned::new(it.with_lifetimes_erased())),
+            mod_path: mod_path.clone(),
+            generic_args,
+        }
+    }
+
+    /// Returns a copy of this path with the segment at `idx` replaced by `f(segment)`, or `None`
+    /// if there is no such segment.
+    pub fn replace_segment<'a>(
+        &'a self,
+        idx: usize,
+        f: impl FnOnce(PathSegment<'a>) -> PathSegment<'a>,
+    ) -> Option<Path> {
+        let Path::Normal { type_anchor, mod_path, generic_args } = self else {
+            return None;
+        };
+        let segment = f(self.segments().get(idx)?);
+
+        let mut segments = mod_path.segments().to_vec();
+        segments[idx] = segment.name.clone();
+        let mut args = match generic_args {
+            Some(it) => it.to_vec(),
+            None => vec![None; segments.len()],
+        };
+        args[idx] = segment.args_and_bindings.cloned().map(Interned::new);
+
+        Some(Path::Normal {
+            type_anchor: type_anchor.clone(),
+            mod_path: Interned::new(ModPath::from_segments(mod_path.kind, segments)),
+            generic_args: args.iter().any(Option::is_some).then(|| args.into()),
+        })
+    }
+
+    /// Like [`Path::replace_segment`], for the first segment.
+    pub fn replace_first_segment<'a>(
+        &'a self,
+        f: impl FnOnce(PathSegment<'a>) -> PathSegment<'a>,
+    ) -> Option<Path> {
+        self.replace_segment(0, f)
+    }
+
+    /// Like [`Path::replace_segment`], for the last segment.
+    pub fn replace_last_segment<'a>(
+        &'a self,
+        f: impl FnOnce(PathSegment<'a>) -> PathSegment<'a>,
+    ) -> Option<Path> {
+        self.replace_segment(self.segments().len().checked_sub(1)?, f)
+    }
+
+    /// Returns whether this path can be a trait bound as found in `dyn Trait`, that is, it is
+    /// neither anchored at a type (`<T>::Foo`) nor a qualified path (`<T as Trait>::Foo`).
+    pub fn is_dyn_trait_path_component(&self) -> bool {
+        match self {
+            Path::Normal { type_anchor: None, .. } => self
+                .segments()
+                .iter()
+                .all(|segment| segment.args_and_bindings.map_or(true, |it| it.self_type.is_none())),
+            Path::Normal { type_anchor: Some(_), .. } | Path::LangItem(..) => false,
+        }
+    }
+
+    /// Returns whether `self` and `other` are equal up to a consistent renaming of type
+    /// parameters, recording the renaming (from names in `self` to names in `other`) in
+    /// `bindings`.
+    ///
+    /// As no name resolution is done, every type that is a plain identifier is considered a type
+    /// parameter. Callers can pre-populate `bindings` with identity mappings for names known to
+    /// refer to concrete types.
+    pub fn alpha_equivalent(&self, other: &Path, bindings: &mut FxHashMap<Name, Name>) -> bool {
+        let (
+            Path::Normal { type_anchor: anchor, mod_path, .. },
+            Path::Normal { type_anchor: other_anchor, mod_path: other_mod_path, .. },
+        ) = (self, other)
+        else {
+            return self == other;
+        };
+        let anchors_equivalent = match (anchor, other_anchor) {
+            (Some(anchor), Some(other_anchor)) => {
+                alpha_equivalent_type_refs(anchor, other_anchor, bindings)
+            }
+            (None, None) => true,
+            _ => false,
+        };
+        anchors_equivalent
+            && mod_path == other_mod_path
+            && self.segments().iter().zip(other.segments().iter()).all(|(segment, other)| {
+                match (segment.args_and_bindings, other.args_and_bindings) {
+                    (Some(args), Some(other)) => {
+                        alpha_equivalent_generic_args(args, other, bindings)
+                    }
+                    (None, None) => true,
+                    _ => false,
+                }
+            })
+    }
+
+    /// Returns whether this path names an item of the `std`/`core` prelude, either directly
+    /// (`Option`) or through the prelude module (`std::prelude::v1::Option`). Bare names are
+    /// looked up in the `std` prelude of `edition`, qualified ones in the prelude they name.
+    ///
+    /// This is a purely syntactic heuristic, no name resolution is done.
+    pub fn is_core_or_std_prelude_item(&self, edition: Edition) -> bool {
+        let Path::Normal { mod_path, type_anchor: None, .. } = self else {
+            return false;
+        };
+        let (krate, edition, name) = match (mod_path.kind, mod_path.segments()) {
+            (PathKind::Plain, [name]) => (StdCrate::Std, edition, name),
+            (PathKind::Plain | PathKind::Abs, [krate, prelude, module, name])
+                if *prelude == name![prelude] =>
+            {
+                let krate = match krate {
+                    _ if *krate == name![std] => StdCrate::Std,
+                    _ if *krate == name![core] => StdCrate::Core,
+                    _ => return false,
+                };
+                let edition = match module {
+                    _ if *module == name![v1] || *module == name![rust_2015] => {
+                        Edition::Edition2015
+                    }
+                    _ if *module == name![rust_2018] => Edition::Edition2018,
+                    _ if *module == name![rust_2021] => Edition::Edition2021,
+                    _ if *module == name![rust_2024] => Edition::Edition2024,
+                    _ => return false,
+                };
+                (krate, edition, name)
+            }
+            _ => return false,
+        };
+        let Some(name) = name.as_str() else { return false };
+        let contains = |items: &[&str]| items.binary_search(&name).is_ok();
+        contains(CORE_PRELUDE_ITEMS)
+            || (krate == StdCrate::Std && contains(STD_PRELUDE_ITEMS))
+            || (edition >= Edition::Edition2021 && contains(RUST_2021_PRELUDE_ITEMS))
+            || (edition >= Edition::Edition2024 && contains(RUST_2024_PRELUDE_ITEMS))
+    }
+
+    /// Returns the standard library crate an absolute path like `::core::mem::swap` points
+    /// into, if any.
+    pub fn std_crate_of(&self) -> Option<StdCrate> {
+        let mod_path = self.mod_path()?;
+        if mod_path.kind != PathKind::Abs {
+            return None;
+        }
+        let krate = mod_path.segments().first()?;
+        if *krate == name![std] {
+            Some(StdCrate::Std)
+        } else if *krate == name![core] {
+            Some(StdCrate::Core)
+        } else if *krate == name![alloc] {
+            Some(StdCrate::Alloc)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_in_std_crate(&self) -> bool {
+        self.std_crate_of().is_some()
+    }
+
     pub fn is_self_type(&self) -> bool {
         let Path::Normal { mod_path, generic_args, type_anchor } = self else {
             return false;
@@ -185,7 +994,53 @@ pub fn is_self_type(&self) -> bool {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+// The prelude lists below are sorted for binary search.
+
+/// Names brought into scope by `core::prelude::v1`, which every edition's prelude includes.
+const CORE_PRELUDE_ITEMS: &[&str] = &[
+    "AsMut",
+    "AsRef",
+    "Clone",
+    "Copy",
+    "Default",
+    "DoubleEndedIterator",
+    "Drop",
+    "Eq",
+    "Err",
+    "ExactSizeIterator",
+    "Extend",
+    "Fn",
+    "FnMut",
+    "FnOnce",
+    "From",
+    "Into",
+    "IntoIterator",
+    "Iterator",
+    "None",
+    "Ok",
+    "Option",
+    "Ord",
+    "PartialEq",
+    "PartialOrd",
+    "Result",
+    "Send",
+    "Sized",
+    "Some",
+    "Sync",
+    "Unpin",
+    "drop",
+];
+
+/// Names the `std` prelude adds on top of the `core` one, in every edition.
+const STD_PRELUDE_ITEMS: &[&str] = &["Box", "String", "ToOwned", "ToString", "Vec"];
+
+/// Names added by the `rust_2021` preludes of `core` and `std`.
+const RUST_2021_PRELUDE_ITEMS: &[&str] = &["FromIterator", "TryFrom", "TryInto"];
+
+/// Names added by the `rust_2024` preludes of `core` and `std`.
+const RUST_2024_PRELUDE_ITEMS: &[&str] = &["Future", "IntoFuture"];
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PathSegment<'a> {
     pub name: &'a Name,
     pub args_and_bindings: Option<&'a GenericArgs>,
@@ -229,6 +1084,10 @@ pub fn take(&self, len: usize) -> PathSegments<'a> {
             generic_args: self.generic_args.map(|it| it.get(..len).unwrap_or(it)),
         }
     }
+    pub fn split_last(&self) -> Option<(PathSegments<'a>, PathSegment<'a>)> {
+        let last = self.last()?;
+        Some((self.take(self.len() - 1), last))
+    }
     pub fn iter(&self) -> impl Iterator<Item = PathSegment<'a>> {
         self.segments
             .iter()
@@ -245,16 +1104,316 @@ pub(crate) fn from_ast(
         lower::lower_generic_args(lower_ctx, node)
     }
 
+    /// Lowers the generic args of a path segment, handling both `Foo<T>` and the `Fn(T) -> U`
+    /// sugar.
+    pub(crate) fn from_path_segment_ast(
+        lower_ctx: &LowerCtx<'_>,
+        segment: &ast::PathSegment,
+    ) -> Option<GenericArgs> {
+        lower::lower_generic_args_from_segment(lower_ctx, segment)
+    }
+
+    /// Creates generic args consisting of the type arguments `types`, like the `A, B` in
+    /// `Foo<A, B>`.
+    pub fn from_types(types: impl IntoIterator<Item = TypeRef>) -> GenericArgs {
+        GenericArgs {
+            args: types.into_iter().map(GenericArg::Type).collect(),
+            ..GenericArgs::empty()
+        }
+    }
+
+    /// Adds the associated type binding `name = type_ref`, like the `Item = T` in
+    /// `Iterator<Item = T>`.
+    pub fn with_binding(mut self, name: Name, type_ref: TypeRef) -> GenericArgs {
+        let mut bindings = std::mem::take(&mut self.bindings).into_vec();
+        bindings.push(AssociatedTypeBinding {
+            name,
+            args: None,
+            type_ref: Some(type_ref),
+            bounds: Box::default(),
+        });
+        self.bindings = bindings.into_boxed_slice();
+        self
+    }
+
+    /// Appends the args and bindings of `other` to these, e.g. merging `<Self=T>` with `<A>`
+    /// gives `<Self=T, A>`. The Self type and whether the args were written with a turbofish are
+    /// taken from `self`, while the result counts as desugared from `Fn(A) -> B` syntax if either
+    /// side does, so that `<Self=T>` merged with the args of `Fn(A)` still prints as `Fn(A)`.
+    pub fn merge(&self, other: &GenericArgs) -> GenericArgs {
+        GenericArgs {
+            args: self.args.iter().chain(other.args.iter()).cloned().collect(),
+            bindings: self.bindings.iter().chain(other.bindings.iter()).cloned().collect(),
+            desugared_from_fn: self.desugared_from_fn || other.desugared_from_fn,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of these generic args without any lifetimes, for comparisons that should
+    /// ignore them: lifetime arguments are removed and lifetimes nested in the remaining
+    /// arguments and bindings are erased with [`TypeRef::with_lifetimes_erased`].
+    pub fn with_lifetimes_erased(&self) -> GenericArgs {
+        GenericArgs {
+            args: self
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArg::Type(ty) => Some(GenericArg::Type(ty.with_lifetimes_erased())),
+                    GenericArg::Lifetime(_) => None,
+                    GenericArg::Const(_) => Some(arg.clone()),
+                })
+                .collect(),
+            self_type: self.self_type.as_ref().map(TypeRef::with_lifetimes_erased),
+            bindings: self
+                .bindings
+                .iter()
+                .map(|binding| AssociatedTypeBinding {
+                    name: binding.name.clone(),
+                    args: binding.args.as_ref().map(|it| Interned::new(it.with_lifetimes_erased())),
+                    type_ref: binding.type_ref.as_ref().map(TypeRef::with_lifetimes_erased),
+                    bounds: bounds_with_lifetimes_erased(&binding.bounds).collect(),
+                })
+                .collect(),
+            desugared_from_fn: self.desugared_from_fn,
+            is_turbofish: self.is_turbofish,
+        }
+    }
+
+    fn walk_type_refs(&self, f: &mut impl FnMut(&TypeRef)) {
+        if let Some(self_type) = &self.self_type {
+            self_type.walk(f);
+        }
+        for arg in self.args.iter() {
+            if let GenericArg::Type(type_ref) = arg {
+                type_ref.walk(f);
+            }
+        }
+        for binding in self.bindings.iter() {
+            if let Some(args) = &binding.args {
+                args.walk_type_refs(f);
+            }
+            if let Some(type_ref) = &binding.type_ref {
+                type_ref.walk(f);
+            }
+            for (path, _) in binding.bounds.iter().filter_map(|bound| bound.as_path()) {
+                path.walk_type_refs(f);
+            }
+        }
+    }
+
+    fn walk_type_refs_mut(&mut self, f: &mut impl FnMut(&mut TypeRef)) {
+        if let Some(self_type) = &mut self.self_type {
+            self_type.walk_mut(f);
+        }
+        for arg in self.args.iter_mut() {
+            if let GenericArg::Type(type_ref) = arg {
+                type_ref.walk_mut(f);
+            }
+        }
+        for binding in self.bindings.iter_mut() {
+            if let Some(args) = &mut binding.args {
+                let mut new_args = GenericArgs::clone(args);
+                new_args.walk_type_refs_mut(f);
+                *args = Interned::new(new_args);
+            }
+            if let Some(type_ref) = &mut binding.type_ref {
+                type_ref.walk_mut(f);
+            }
+            walk_bounds_mut(&mut binding.bounds, f);
+        }
+    }
+
+    /// Returns the indices into `args` of the type arguments.
+    pub fn positions_of_type_args(&self) -> impl Iterator<Item = usize> + '_ {
+        self.positions_of(|arg| matches!(arg, GenericArg::Type(_)))
+    }
+
+    /// Returns the indices into `args` of the lifetime arguments.
+    pub fn positions_of_lifetime_args(&self) -> impl Iterator<Item = usize> + '_ {
+        self.positions_of(|arg| matches!(arg, GenericArg::Lifetime(_)))
+    }
+
+    /// Returns the indices into `args` of the const arguments.
+    pub fn positions_of_const_args(&self) -> impl Iterator<Item = usize> + '_ {
+        self.positions_of(|arg| matches!(arg, GenericArg::Const(_)))
+    }
+
+    /// Returns the `type_arg_index`-th type argument, counting only type arguments.
+    pub fn type_arg_at(&self, type_arg_index: usize) -> Option<&TypeRef> {
+        self.args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArg::Type(type_ref) => Some(type_ref),
+                GenericArg::Lifetime(_) | GenericArg::Const(_) => None,
+            })
+            .nth(type_arg_index)
+    }
+
+    /// Returns, for each type argument, its name if it is a plain identifier like `T`, for use in
+    /// diagnostics. Returns `None` if there are no type arguments.
+    pub fn type_arg_names(&self) -> Option<Vec<Option<Name>>> {
+        let names: Vec<_> = self
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArg::Type(type_ref) => Some(type_ref_name(type_ref)),
+                GenericArg::Lifetime(_) | GenericArg::Const(_) => None,
+            })
+            .collect();
+        (!names.is_empty()).then_some(names)
+    }
+
+    /// Returns the name of the `type_arg_index`-th type argument if it is a plain identifier,
+    /// see [`GenericArgs::type_arg_names`].
+    pub fn named_type_arg_at(&self, type_arg_index: usize) -> Option<Name> {
+        type_ref_name(self.type_arg_at(type_arg_index)?)
+    }
+
+    /// Reorders the arguments so that their kinds line up with `params`: the n-th type, const
+    /// and lifetime argument is moved to the position of the n-th type, const and lifetime
+    /// parameter respectively. Returns `None` if the arguments don't fit the parameters.
+    ///
+    /// Note that const arguments written as a bare path (`Foo<N>`) are lowered as type
+    /// arguments, so they only match type parameters.
+    pub fn reorder_to_match_params(&self, params: &[GenericParamId]) -> Option<GenericArgs> {
+        if self.args.len() != params.len() {
+            return None;
+        }
+
+        let mut types = self.args.iter().filter(|it| matches!(it, GenericArg::Type(_)));
+        let mut consts = self.args.iter().filter(|it| matches!(it, GenericArg::Const(_)));
+        let mut lifetimes = self.args.iter().filter(|it| matches!(it, GenericArg::Lifetime(_)));
+        let args = params
+            .iter()
+            .map(|param| match param {
+                GenericParamId::TypeParamId(_) => types.next(),
+                GenericParamId::ConstParamId(_) => consts.next(),
+                GenericParamId::LifetimeParamId(_) => lifetimes.next(),
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(GenericArgs { args: args.into_iter().cloned().collect(), ..self.clone() })
+    }
+
+    fn positions_of(
+        &self,
+        pred: impl Fn(&GenericArg) -> bool + 'static,
+    ) -> impl Iterator<Item = usize> + '_ {
+        self.args.iter().enumerate().filter(move |(_, arg)| pred(arg)).map(|(idx, _)| idx)
+    }
+
     pub(crate) fn empty() -> GenericArgs {
         GenericArgs {
             args: Box::default(),
-            has_self_type: false,
+            self_type: None,
             bindings: Box::default(),
             desugared_from_fn: false,
+            is_turbofish: false,
         }
     }
 }
 
+fn alpha_equivalent_generic_args(
+    args: &GenericArgs,
+    other: &GenericArgs,
+    bindings: &mut FxHashMap<Name, Name>,
+) -> bool {
+    let self_types_equivalent = match (&args.self_type, &other.self_type) {
+        (Some(ty), Some(other)) => alpha_equivalent_type_refs(ty, other, bindings),
+        (None, None) => true,
+        _ => false,
+    };
+    self_types_equivalent
+        && args.desugared_from_fn == other.desugared_from_fn
+        && args.args.len() == other.args.len()
+        && args.args.iter().zip(other.args.iter()).all(|(arg, other)| match (arg, other) {
+            (GenericArg::Type(ty), GenericArg::Type(other)) => {
+                alpha_equivalent_type_refs(ty, other, bindings)
+            }
+            _ => arg == other,
+        })
+        && args.bindings.len() == other.bindings.len()
+        && args.bindings.iter().zip(other.bindings.iter()).all(|(binding, other)| {
+            let args_equivalent = match (&binding.args, &other.args) {
+                (Some(args), Some(other)) => alpha_equivalent_generic_args(args, other, bindings),
+                (None, None) => true,
+                _ => false,
+            };
+            let type_refs_equivalent = match (&binding.type_ref, &other.type_ref) {
+                (Some(ty), Some(other)) => alpha_equivalent_type_refs(ty, other, bindings),
+                (None, None) => true,
+                _ => false,
+            };
+            binding.name == other.name
+                && args_equivalent
+                && type_refs_equivalent
+                && binding.bounds == other.bounds
+        })
+}
+
+fn alpha_equivalent_type_refs(
+    ty: &TypeRef,
+    other: &TypeRef,
+    bindings: &mut FxHashMap<Name, Name>,
+) -> bool {
+    match (ty, other) {
+        (TypeRef::Path(path), TypeRef::Path(other)) => {
+            match (type_param_name(path), type_param_name(other)) {
+                (Some(name), Some(other)) => match bindings.get(name) {
+                    Some(bound) => bound == other,
+                    // The renaming has to be a bijection.
+                    None if bindings.values().any(|it| it == other) => false,
+                    None => {
+                        bindings.insert(name.clone(), other.clone());
+                        true
+                    }
+                },
+                _ => path.alpha_equivalent(other, bindings),
+            }
+        }
+        (TypeRef::Tuple(tys), TypeRef::Tuple(others)) => {
+            tys.len() == others.len()
+                && tys
+                    .iter()
+                    .zip(others)
+                    .all(|(ty, other)| alpha_equivalent_type_refs(ty, other, bindings))
+        }
+        (TypeRef::RawPtr(ty, mutability), TypeRef::RawPtr(other, other_mutability)) => {
+            mutability == other_mutability && alpha_equivalent_type_refs(ty, other, bindings)
+        }
+        (
+            TypeRef::Reference(ty, lifetime, mutability),
+            TypeRef::Reference(other, other_lifetime, other_mutability),
+        ) => {
+            lifetime == other_lifetime
+                && mutability == other_mutability
+                && alpha_equivalent_type_refs(ty, other, bindings)
+        }
+        (TypeRef::Array(ty, len), TypeRef::Array(other, other_len)) => {
+            len == other_len && alpha_equivalent_type_refs(ty, other, bindings)
+        }
+        (TypeRef::Slice(ty), TypeRef::Slice(other)) => {
+            alpha_equivalent_type_refs(ty, other, bindings)
+        }
+        _ => ty == other,
+    }
+}
+
+/// Returns the name of `path` if it could refer to a type parameter.
+fn type_param_name(path: &Path) -> Option<&Name> {
+    match path {
+        Path::Normal { type_anchor: None, mod_path, generic_args: None } => mod_path.as_ident(),
+        _ => None,
+    }
+}
+
+fn type_ref_name(type_ref: &TypeRef) -> Option<Name> {
+    match type_ref {
+        TypeRef::Path(path) => type_param_name(path).cloned(),
+        _ => None,
+    }
+}
+
 impl From<Name> for Path {
     fn from(name: Name) -> Path {
         Path::Normal {
@@ -0,0 +1,77 @@
+//! A builder for constructing paths in code, e.g. when desugaring expressions.
+
+use hir_expand::name::Name;
+use intern::Interned;
+
+use crate::{
+    path::{GenericArg, GenericArgs, ModPath, Path, PathKind},
+    type_ref::TypeRef,
+};
+
+/// Builds a [`Path`] segment by segment, like `PathBuilder::new(PathKind::Abs)
+/// .segment(name![core]).segment(name![iter]).segment_with_args(name![IntoIterator], [ty])
+/// .finish()`.
+#[derive(Debug, Clone)]
+pub struct PathBuilder {
+    kind: PathKind,
+    segments: Vec<Name>,
+    generic_args: Vec<Option<GenericArgs>>,
+}
+
+impl PathBuilder {
+    pub fn new(kind: PathKind) -> PathBuilder {
+        PathBuilder { kind, segments: Vec::new(), generic_args: Vec::new() }
+    }
+
+    pub fn kind(mut self, kind: PathKind) -> PathBuilder {
+        self.kind = kind;
+        self
+    }
+
+    /// Appends a segment without generic args.
+    pub fn segment(mut self, name: Name) -> PathBuilder {
+        self.segments.push(name);
+        self.generic_args.push(None);
+        self
+    }
+
+    /// Appends a segment with the generic args `args`.
+    pub fn segment_with_args(
+        mut self,
+        name: Name,
+        args: impl IntoIterator<Item = GenericArg>,
+    ) -> PathBuilder {
+        let generic_args = GenericArgs { args: args.into_iter().collect(), ..GenericArgs::empty() };
+        self.segments.push(name);
+        self.generic_args.push(Some(generic_args));
+        self
+    }
+
+    /// Adds the associated type binding `name = type_ref` to the last segment, like the
+    /// `Output = T` in `FnOnce<(), Output = T>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no segment has been added yet.
+    pub fn binding(mut self, name: Name, type_ref: TypeRef) -> PathBuilder {
+        let generic_args =
+            self.generic_args.last_mut().expect("binding added to a path without segments");
+        *generic_args = Some(
+            generic_args.take().unwrap_or_else(GenericArgs::empty).with_binding(name, type_ref),
+        );
+        self
+    }
+
+    pub fn finish(self) -> Path {
+        let generic_args = self
+            .generic_args
+            .iter()
+            .any(Option::is_some)
+            .then(|| self.generic_args.into_iter().map(|it| it.map(Interned::new)).collect());
+        Path::Normal {
+            type_anchor: None,
+            mod_path: Interned::new(ModPath::from_segments(self.kind, self.segments)),
+            generic_args,
+        }
+    }
+}
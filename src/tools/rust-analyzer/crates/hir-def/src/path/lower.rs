@@ -1,7 +1,5 @@
 //! Transforms syntax into `Path` objects, ideally with accounting for hygiene
 
-use std::iter;
-
 use crate::{lower::LowerCtx, type_ref::ConstRef};
 
 use hir_expand::{
@@ -12,26 +10,31 @@
 use syntax::ast::{self, AstNode, HasTypeBounds};
 
 use crate::{
-    path::{AssociatedTypeBinding, GenericArg, GenericArgs, ModPath, Path, PathKind},
+    path::{
+        AssociatedTypeBinding, GenericArg, GenericArgs, ModPath, Path, PathKind, PathLoweringError,
+    },
     type_ref::{LifetimeRef, TypeBound, TypeRef},
 };
 
 /// Converts an `ast::Path` to `Path`. Works with use trees.
 /// It correctly handles `$crate` based path from macro call.
-pub(super) fn lower_path(ctx: &LowerCtx<'_>, mut path: ast::Path) -> Option<Path> {
+pub(super) fn lower_path(
+    ctx: &LowerCtx<'_>,
+    mut path: ast::Path,
+) -> Result<Path, PathLoweringError> {
     let mut kind = PathKind::Plain;
     let mut type_anchor = None;
     let mut segments = Vec::new();
     let mut generic_args = Vec::new();
     let span_map = ctx.span_map();
     loop {
-        let segment = path.segment()?;
+        let segment = path.segment().ok_or(PathLoweringError::MissingSegment)?;
 
         if segment.coloncolon_token().is_some() {
             kind = PathKind::Abs;
         }
 
-        match segment.kind()? {
+        match segment.kind().ok_or(PathLoweringError::MissingSegment)? {
             ast::PathSegmentKind::Name(name_ref) => {
                 if name_ref.text() == "$crate" {
                     break kind = resolve_crate_root(
@@ -42,17 +45,7 @@ pub(super) fn lower_path(ctx: &LowerCtx<'_>, mut path: ast::Path) -> Option<Path
                     .unwrap_or(PathKind::Crate);
                 }
                 let name = name_ref.as_name();
-                let args = segment
-                    .generic_arg_list()
-                    .and_then(|it| lower_generic_args(ctx, it))
-                    .or_else(|| {
-                        lower_generic_args_from_fn_path(
-                            ctx,
-                            segment.param_list(),
-                            segment.ret_type(),
-                        )
-                    })
-                    .map(Interned::new);
+                let args = GenericArgs::from_path_segment_ast(ctx, &segment).map(Interned::new);
                 if args.is_some() {
                     generic_args.resize(segments.len(), None);
                     generic_args.push(args);
@@ -65,9 +58,14 @@ pub(super) fn lower_path(ctx: &LowerCtx<'_>, mut path: ast::Path) -> Option<Path
             ast::PathSegmentKind::Type { type_ref, trait_ref } => {
                 assert!(path.qualifier().is_none()); // this can only occur at the first segment
 
-                let self_type = TypeRef::from_ast(ctx, type_ref?);
+                let type_ref = type_ref.ok_or(PathLoweringError::MissingSelfType)?;
+                let self_type = TypeRef::from_ast(ctx, type_ref);
 
                 match trait_ref {
+                    // <T as >::foo, the trait path couldn't be parsed
+                    None if segment.as_token().is_some() => {
+                        return Err(PathLoweringError::InvalidQualifiedTrait);
+                    }
                     // <T>::foo
                     None => {
                         type_anchor = Some(Interned::new(self_type));
@@ -75,10 +73,12 @@ pub(super) fn lower_path(ctx: &LowerCtx<'_>, mut path: ast::Path) -> Option<Path
                     }
                     // <T as Trait<A>>::Foo desugars to Trait<Self=T, A>::Foo
                     Some(trait_ref) => {
-                        let Path::Normal { mod_path, generic_args: path_generic_args, .. } =
-                            Path::from_src(ctx, trait_ref.path()?)?
+                        let trait_path =
+                            trait_ref.path().ok_or(PathLoweringError::InvalidQualifiedTrait)?;
+                        let Ok(Path::Normal { mod_path, generic_args: path_generic_args, .. }) =
+                            lower_path(ctx, trait_path)
                         else {
-                            return None;
+                            return Err(PathLoweringError::InvalidQualifiedTrait);
                         };
                         let num_segments = mod_path.segments().len();
                         kind = mod_path.kind;
@@ -91,26 +91,15 @@ pub(super) fn lower_path(ctx: &LowerCtx<'_>, mut path: ast::Path) -> Option<Path
                             generic_args.resize(segments.len(), None);
                         }
 
-                        let self_type = GenericArg::Type(self_type);
-
                         // Insert the type reference (T in the above example) as Self parameter for the trait
-                        let last_segment = generic_args.get_mut(segments.len() - num_segments)?;
-                        *last_segment = Some(Interned::new(match last_segment.take() {
-                            Some(it) => GenericArgs {
-                                args: iter::once(self_type)
-                                    .chain(it.args.iter().cloned())
-                                    .collect(),
-
-                                has_self_type: true,
-                                bindings: it.bindings.clone(),
-                                desugared_from_fn: it.desugared_from_fn,
-                            },
-                            None => GenericArgs {
-                                args: Box::new([self_type]),
-                                has_self_type: true,
-                                ..GenericArgs::empty()
-                            },
-                        }));
+                        let last_segment = generic_args
+                            .get_mut(segments.len() - num_segments)
+                            .ok_or(PathLoweringError::InvalidQualifiedTrait)?;
+                        let mut trait_args = last_segment
+                            .take()
+                            .map_or_else(GenericArgs::empty, |it| GenericArgs::clone(&it));
+                        trait_args.self_type = Some(self_type);
+                        *last_segment = Some(Interned::new(trait_args));
                     }
                 }
             }
@@ -153,7 +142,8 @@ pub(super) fn lower_path(ctx: &LowerCtx<'_>, mut path: ast::Path) -> Option<Path
     // We follow what it did anyway :)
     if segments.len() == 1 && kind == PathKind::Plain {
         if let Some(_macro_call) = path.syntax().parent().and_then(ast::MacroCall::cast) {
-            let syn_ctxt = span_map.span_for_range(path.segment()?.syntax().text_range()).ctx;
+            let segment = path.segment().ok_or(PathLoweringError::MissingSegment)?;
+            let syn_ctxt = span_map.span_for_range(segment.syntax().text_range()).ctx;
             if let Some(macro_call_id) = ctx.db.lookup_intern_syntax_context(syn_ctxt).outer_expn {
                 if ctx.db.lookup_intern_macro_call(macro_call_id).def.local_inner {
                     kind = match resolve_crate_root(ctx.db.upcast(), syn_ctxt) {
@@ -166,7 +156,7 @@ pub(super) fn lower_path(ctx: &LowerCtx<'_>, mut path: ast::Path) -> Option<Path
     }
 
     let mod_path = Interned::new(ModPath::from_segments(kind, segments));
-    return Some(Path::Normal {
+    return Ok(Path::Normal {
         type_anchor,
         mod_path,
         generic_args: if generic_args.is_empty() { None } else { Some(generic_args.into()) },
@@ -184,6 +174,19 @@ fn qualifier(path: &ast::Path) -> Option<ast::Path> {
     }
 }
 
+/// Lowers the generic args of a single path segment, trying the angle-bracketed form
+/// (`Foo<T>`) first and falling back to the parenthesized form of the `Fn` traits
+/// (`Fn(T) -> U`).
+pub(super) fn lower_generic_args_from_segment(
+    ctx: &LowerCtx<'_>,
+    segment: &ast::PathSegment,
+) -> Option<GenericArgs> {
+    segment
+        .generic_arg_list()
+        .and_then(|it| lower_generic_args(ctx, it))
+        .or_else(|| lower_generic_args_from_fn_path(ctx, segment.param_list(), segment.ret_type()))
+}
+
 pub(super) fn lower_generic_args(
     lower_ctx: &LowerCtx<'_>,
     node: ast::GenericArgList,
@@ -236,9 +239,10 @@ pub(super) fn lower_generic_args(
     }
     Some(GenericArgs {
         args: args.into_boxed_slice(),
-        has_self_type: false,
+        self_type: None,
         bindings: bindings.into_boxed_slice(),
         desugared_from_fn: false,
+        is_turbofish: node.coloncolon_token().is_some(),
     })
 }
 
@@ -274,5 +278,11 @@ fn lower_generic_args_from_fn_path(
             bounds: Box::default(),
         }])
     };
-    Some(GenericArgs { args, has_self_type: false, bindings, desugared_from_fn: true })
+    Some(GenericArgs {
+        args,
+        self_type: None,
+        bindings,
+        desugared_from_fn: true,
+        is_turbofish: false,
+    })
 }
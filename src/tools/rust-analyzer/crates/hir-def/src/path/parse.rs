@@ -0,0 +1,95 @@
+//! Parsing of paths from strings, for tests and built-in desugarings.
+
+use std::{cell::OnceCell, fmt};
+
+use hir_expand::span_map::{RealSpanMap, SpanMap};
+use span::FileId;
+use syntax::{
+    ast::{self, AstNode},
+    TextRange, TextSize,
+};
+use triomphe::Arc;
+
+use crate::{db::DefDatabase, lower::LowerCtx, path::Path, type_ref::ConstRef};
+
+/// The error returned by [`Path::parse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathParseError {
+    /// The text is not a single path.
+    NotAPath,
+    /// The segment at `index` has a syntax error.
+    InvalidSegment { index: usize, segment: String },
+    /// The segment at `index` contains a block or expression const argument or a macro, which
+    /// can only be lowered from a source file.
+    UnsupportedSegment { index: usize, segment: String },
+}
+
+impl fmt::Display for PathParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathParseError::NotAPath => f.write_str("not a path"),
+            PathParseError::InvalidSegment { index, segment } => {
+                write!(f, "invalid path segment {index}: `{segment}`")
+            }
+            PathParseError::UnsupportedSegment { index, segment } => {
+                write!(f, "unsupported path segment {index}: `{segment}`")
+            }
+        }
+    }
+}
+
+impl Path {
+    /// Parses `text` as a path in type position, like `std::ops::Try` or
+    /// `<T as Iterator>::Item`.
+    pub fn parse(db: &dyn DefDatabase, text: &str) -> Result<Path, PathParseError> {
+        const PREFIX: &str = "type T = ";
+        let parse = ast::SourceFile::parse(&format!("{PREFIX}{text};"));
+        let text_range = TextRange::at(TextSize::of(PREFIX), TextSize::of(text));
+        let path = parse
+            .tree()
+            .syntax()
+            .descendants()
+            .find_map(ast::Path::cast)
+            .ok_or(PathParseError::NotAPath)?;
+
+        let segments: Vec<_> = path.segments().collect();
+        // Errors between segments, e.g. a missing segment after `::`, are attributed to the
+        // following one.
+        let segment_at = |offset| {
+            if !path.syntax().text_range().contains_inclusive(offset) {
+                return Err(PathParseError::NotAPath);
+            }
+            let index = segments
+                .iter()
+                .position(|it| it.syntax().text_range().contains_inclusive(offset))
+                .unwrap_or_else(|| {
+                    segments.iter().filter(|it| it.syntax().text_range().end() <= offset).count()
+                });
+            let segment = segments.get(index).map_or_else(String::new, |it| it.to_string());
+            Ok((index, segment))
+        };
+        // Check that the path is all of `text` first, as the parser reports trailing input like
+        // the `bar` of `foo bar` at the end of the path.
+        if path.syntax().text_range() != text_range {
+            return Err(PathParseError::NotAPath);
+        }
+        if let Some(err) = parse.errors().first() {
+            let (index, segment) = segment_at(err.range().start())?;
+            return Err(PathParseError::InvalidSegment { index, segment });
+        }
+        // Lowering these needs the `AstId` of the node, which only nodes of real files have.
+        let unsupported =
+            path.syntax().descendants().find(|it| match ast::ConstArg::cast(it.clone()) {
+                Some(arg) => arg.expr().is_some_and(|it| ConstRef::needs_ast_id(&it)),
+                None => ast::MacroType::can_cast(it.kind()),
+            });
+        if let Some(node) = unsupported {
+            let (index, segment) = segment_at(node.text_range().start())?;
+            return Err(PathParseError::UnsupportedSegment { index, segment });
+        }
+
+        let span_map = SpanMap::RealSpanMap(Arc::new(RealSpanMap::absolute(FileId::BOGUS)));
+        let ctx = LowerCtx::with_span_map_cell(db, FileId::BOGUS.into(), OnceCell::from(span_map));
+        Path::from_src(&ctx, path).ok_or(PathParseError::NotAPath)
+    }
+}
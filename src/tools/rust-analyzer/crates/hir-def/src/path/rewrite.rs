@@ -0,0 +1,105 @@
+//! Rewriting of the paths embedded in type references.
+
+use intern::Interned;
+
+use crate::{
+    path::{AssociatedTypeBinding, GenericArg, GenericArgs, Path},
+    type_ref::{TypeBound, TypeRef},
+};
+
+/// Clones `ty`, replacing every path `p` in it for which `f(p)` returns `Some`. Paths that are
+/// kept have their type anchor and generic args rewritten in turn; replacement paths are not
+/// visited again.
+pub fn rewrite_all_paths(ty: &TypeRef, f: impl Fn(&Path) -> Option<Path>) -> TypeRef {
+    rewrite_type_ref(ty, &f)
+}
+
+fn rewrite_type_ref(ty: &TypeRef, f: &dyn Fn(&Path) -> Option<Path>) -> TypeRef {
+    match ty {
+        TypeRef::Path(path) => TypeRef::Path(rewrite_path(path, f)),
+        TypeRef::Tuple(types) => {
+            TypeRef::Tuple(types.iter().map(|ty| rewrite_type_ref(ty, f)).collect())
+        }
+        TypeRef::RawPtr(inner, mutability) => {
+            TypeRef::RawPtr(Box::new(rewrite_type_ref(inner, f)), *mutability)
+        }
+        TypeRef::Reference(inner, lifetime, mutability) => {
+            TypeRef::Reference(Box::new(rewrite_type_ref(inner, f)), lifetime.clone(), *mutability)
+        }
+        TypeRef::Array(inner, len) => {
+            TypeRef::Array(Box::new(rewrite_type_ref(inner, f)), len.clone())
+        }
+        TypeRef::Slice(inner) => TypeRef::Slice(Box::new(rewrite_type_ref(inner, f))),
+        TypeRef::Fn(params, is_varargs, is_unsafe, abi) => TypeRef::Fn(
+            params.iter().map(|(name, ty)| (name.clone(), rewrite_type_ref(ty, f))).collect(),
+            *is_varargs,
+            *is_unsafe,
+            abi.clone(),
+        ),
+        TypeRef::ImplTrait(bounds) => TypeRef::ImplTrait(rewrite_bounds(bounds, f).collect()),
+        TypeRef::DynTrait(bounds) => TypeRef::DynTrait(rewrite_bounds(bounds, f).collect()),
+        TypeRef::Never | TypeRef::Placeholder | TypeRef::Macro(_) | TypeRef::Error => ty.clone(),
+    }
+}
+
+fn rewrite_path(path: &Path, f: &dyn Fn(&Path) -> Option<Path>) -> Path {
+    if let Some(new_path) = f(path) {
+        return new_path;
+    }
+    match path {
+        Path::Normal { type_anchor, mod_path, generic_args } => Path::Normal {
+            type_anchor: type_anchor.as_ref().map(|it| Interned::new(rewrite_type_ref(it, f))),
+            mod_path: mod_path.clone(),
+            generic_args: generic_args.as_ref().map(|generic_args| {
+                generic_args
+                    .iter()
+                    .map(|args| args.as_ref().map(|it| Interned::new(rewrite_generic_args(it, f))))
+                    .collect()
+            }),
+        },
+        Path::LangItem(..) => path.clone(),
+    }
+}
+
+fn rewrite_generic_args(args: &GenericArgs, f: &dyn Fn(&Path) -> Option<Path>) -> GenericArgs {
+    GenericArgs {
+        args: args
+            .args
+            .iter()
+            .map(|arg| match arg {
+                GenericArg::Type(ty) => GenericArg::Type(rewrite_type_ref(ty, f)),
+                GenericArg::Lifetime(_) | GenericArg::Const(_) => arg.clone(),
+            })
+            .collect(),
+        self_type: args.self_type.as_ref().map(|it| rewrite_type_ref(it, f)),
+        bindings: args
+            .bindings
+            .iter()
+            .map(|binding| AssociatedTypeBinding {
+                name: binding.name.clone(),
+                args: binding.args.as_ref().map(|it| Interned::new(rewrite_generic_args(it, f))),
+                type_ref: binding.type_ref.as_ref().map(|it| rewrite_type_ref(it, f)),
+                bounds: rewrite_bounds(&binding.bounds, f).collect(),
+            })
+            .collect(),
+        desugared_from_fn: args.desugared_from_fn,
+        is_turbofish: args.is_turbofish,
+    }
+}
+
+fn rewrite_bounds<'a>(
+    bounds: &'a [Interned<TypeBound>],
+    f: &'a dyn Fn(&Path) -> Option<Path>,
+) -> impl Iterator<Item = Interned<TypeBound>> + 'a {
+    bounds.iter().map(move |bound| {
+        Interned::new(match &**bound {
+            TypeBound::Path(path, modifier) => {
+                TypeBound::Path(rewrite_path(path, f), modifier.clone())
+            }
+            TypeBound::ForLifetime(lifetimes, path) => {
+                TypeBound::ForLifetime(lifetimes.clone(), rewrite_path(path, f))
+            }
+            TypeBound::Lifetime(_) | TypeBound::Error => TypeBound::clone(bound),
+        })
+    })
+}
@@ -0,0 +1,1122 @@
+use std::hash::Hasher;
+
+use base_db::{CrateId, SourceDatabase};
+use expect_test::{expect, Expect};
+use hir_expand::{
+    db::ExpandDatabase,
+    name::{name, Name},
+};
+use rustc_hash::{FxHashMap, FxHasher};
+use span::Edition;
+use syntax::ast::{self, AstNode};
+use test_fixture::WithFixture;
+
+use crate::{
+    db::DefDatabase,
+    generics::TypeOrConstParamData,
+    hir::type_ref::TypeRef,
+    lang_item::LangItem,
+    lower::LowerCtx,
+    nameres::DefMap,
+    path::{
+        rewrite, CratePrefix, GenericArg, GenericArgs, ImportAlias, ModPath, Path, PathBuilder,
+        PathKind, PathLoweringError, PathParseError, PathSegment, PrettyPrintOpts, StdCrate,
+    },
+    pretty::{print_path, print_type_ref},
+    test_db::TestDB,
+    AdtId, ConstParamId, GenericDefId, GenericParamId, ModuleDefId, TypeOrConstParamId,
+    TypeParamId,
+};
+
+/// Lowers the first path found in `type T = {path};`.
+fn lower(path: &str) -> (TestDB, Path) {
+    lower_first_path(&format!("type T = {path};"))
+}
+
+fn lower_first_path(ra_fixture: &str) -> (TestDB, Path) {
+    let (db, file_id) = TestDB::with_single_file(ra_fixture);
+    let file = db.parse(file_id).tree();
+    let ast_path = file.syntax().descendants().find_map(ast::Path::cast).unwrap();
+    let path = {
+        let ctx = LowerCtx::new(&db, file_id.into());
+        Path::from_src(&ctx, ast_path).unwrap()
+    };
+    (db, path)
+}
+
+fn check(path: &str, expect: Expect) {
+    let (db, path) = lower(path);
+    let mut buf = String::new();
+    print_path(&db, &path, &mut buf).unwrap();
+    expect.assert_eq(&buf);
+}
+
+/// Returns the crate with the display name `name`.
+fn crate_by_name(db: &TestDB, name: &str) -> CrateId {
+    let crate_graph = db.crate_graph();
+    let krate = crate_graph
+        .iter()
+        .find(|&it| {
+            crate_graph[it].display_name.as_ref().is_some_and(|it| &**it.crate_name() == name)
+        })
+        .unwrap();
+    krate
+}
+
+#[test]
+fn fn_like_generic_args() {
+    check("FnMut(i32) -> bool", expect![[r#"FnMut<(i32), Output = bool>"#]]);
+    check("Fn()", expect![[r#"Fn<(), Output = ()>"#]]);
+
+    let (_, path) = lower("FnMut(i32) -> bool");
+    let args = path.segments().last().unwrap().args_and_bindings.unwrap();
+    assert!(args.desugared_from_fn);
+    assert_eq!(args.bindings.len(), 1);
+}
+
+#[test]
+fn angle_bracketed_generic_args() {
+    let (_, path) = lower("Vec<u8>");
+    let args = path.segments().last().unwrap().args_and_bindings.unwrap();
+    assert!(!args.desugared_from_fn);
+    assert!(!args.is_turbofish);
+    assert_eq!(args.args.len(), 1);
+}
+
+#[test]
+fn turbofish_generic_args() {
+    let (_, path) = lower_first_path("fn f() { Vec::<u8>::new(); }");
+    let args = path.segments().first().unwrap().args_and_bindings.unwrap();
+    assert!(args.is_turbofish);
+
+    let (_, path) = lower("Vec::<u8>");
+    assert!(path.segments().last().unwrap().args_and_bindings.unwrap().is_turbofish);
+}
+
+#[test]
+fn pretty_print_opts() {
+    let (db, path) = lower("::std::collections::HashMap<String, Vec<u8>>");
+    let check = |opts: PrettyPrintOpts, expect: Expect| {
+        let mut buf = String::new();
+        path.pretty_print(&db, &mut buf, opts).unwrap();
+        expect.assert_eq(&buf);
+    };
+    let default = PrettyPrintOpts::default();
+
+    check(default, expect![[r#"::std::collections::HashMap<String, Vec<u8>>"#]]);
+    check(
+        PrettyPrintOpts { show_generic_args: false, ..default },
+        expect![[r#"::std::collections::HashMap"#]],
+    );
+    check(
+        PrettyPrintOpts { use_short_std: true, ..default },
+        expect![[r#"collections::HashMap<String, Vec<u8>>"#]],
+    );
+    check(
+        PrettyPrintOpts { show_leading_separator: false, ..default },
+        expect![[r#"std::collections::HashMap<String, Vec<u8>>"#]],
+    );
+    check(
+        PrettyPrintOpts { show_generic_args: false, use_short_std: true, ..default },
+        expect![[r#"collections::HashMap"#]],
+    );
+    check(
+        PrettyPrintOpts { show_generic_args: false, show_leading_separator: false, ..default },
+        expect![[r#"std::collections::HashMap"#]],
+    );
+}
+
+#[test]
+fn prelude_items() {
+    let is_prelude = |path, edition| lower(path).1.is_core_or_std_prelude_item(edition);
+    assert!(is_prelude("Option", Edition::Edition2015));
+    assert!(is_prelude("Result", Edition::Edition2021));
+    assert!(is_prelude("Vec", Edition::Edition2018));
+    assert!(is_prelude("std::prelude::v1::Option", Edition::Edition2021));
+    assert!(is_prelude("::core::prelude::rust_2021::Result", Edition::Edition2015));
+    assert!(!is_prelude("HashMap", Edition::Edition2021));
+    assert!(!is_prelude("std::option::Option", Edition::Edition2021));
+    assert!(!is_prelude("crate::Option", Edition::Edition2021));
+
+    // `Vec`, `String`, `Box`, `ToString` and `ToOwned` are only in the `std` prelude.
+    assert!(is_prelude("std::prelude::v1::ToString", Edition::Edition2021));
+    assert!(!is_prelude("core::prelude::v1::ToString", Edition::Edition2021));
+    assert!(!is_prelude("::core::prelude::rust_2021::Vec", Edition::Edition2021));
+
+    // Edition preludes only add items, the edition of a qualified path is the one it names.
+    assert!(!is_prelude("TryFrom", Edition::Edition2018));
+    assert!(is_prelude("TryFrom", Edition::Edition2021));
+    assert!(!is_prelude("std::prelude::rust_2018::TryInto", Edition::Edition2021));
+    assert!(is_prelude("core::prelude::rust_2021::FromIterator", Edition::Edition2015));
+    assert!(!is_prelude("Future", Edition::Edition2021));
+    assert!(is_prelude("Future", Edition::Edition2024));
+    assert!(is_prelude("std::prelude::rust_2024::IntoFuture", Edition::Edition2021));
+    assert!(!is_prelude("std::prelude::rust_2025::Option", Edition::Edition2021));
+}
+
+#[test]
+fn generic_arg_positions() {
+    let (_, path) = lower("Foo<'a, T, 3, U>");
+    let args = path.segments().last().unwrap().args_and_bindings.unwrap();
+    assert_eq!(args.positions_of_type_args().collect::<Vec<_>>(), [1, 3]);
+    assert_eq!(args.positions_of_lifetime_args().collect::<Vec<_>>(), [0]);
+    assert_eq!(args.positions_of_const_args().collect::<Vec<_>>(), [2]);
+    let Some(TypeRef::Path(u)) = args.type_arg_at(1) else { panic!("expected a path") };
+    assert_eq!(u.mod_path().and_then(|it| it.as_ident()).and_then(|it| it.as_str()), Some("U"));
+    assert_eq!(args.type_arg_at(2), None);
+}
+
+#[test]
+fn paths_in_macro_expansion() {
+    let db = TestDB::with_files(
+        r#"
+//- /main.rs crate:main deps:lib
+lib::m!();
+
+//- /lib.rs crate:lib
+#[macro_export]
+macro_rules! m {
+    () => {
+        $crate::foo!();
+        bar!();
+    };
+}
+#[macro_export]
+macro_rules! foo {
+    () => {};
+}
+"#,
+    );
+    let def_map = db.crate_def_map(crate_by_name(&db, "main"));
+    let (_, &call_id) = def_map[DefMap::ROOT]
+        .scope
+        .iter_macro_invoc()
+        .find(|(ast_id, _)| !ast_id.file_id.is_macro())
+        .unwrap();
+    // Paths in the expansion are lowered with the expansion's span map, which knows where
+    // `$crate` came from.
+    let ctx = LowerCtx::new(&db, call_id.as_file());
+    let paths = db
+        .parse_or_expand(call_id.as_file())
+        .descendants()
+        .filter_map(ast::MacroCall::cast)
+        .map(|call| Path::from_src(&ctx, call.path().unwrap()).unwrap())
+        .collect::<Vec<_>>();
+
+    let [dollar_crate, bare] = &*paths else { panic!("expected two macro calls") };
+    assert_eq!(*dollar_crate.kind(), PathKind::DollarCrate(crate_by_name(&db, "lib")));
+    assert_eq!(
+        dollar_crate.segments().iter().map(|it| it.name.as_str()).collect::<Vec<_>>(),
+        [Some("foo")]
+    );
+    assert_eq!(*bare.kind(), PathKind::Plain);
+    assert_eq!(
+        bare.segments().iter().map(|it| it.name.as_str()).collect::<Vec<_>>(),
+        [Some("bar")]
+    );
+}
+
+#[test]
+fn display_resolves_dollar_crate() {
+    let (db, _) = TestDB::with_single_file("//- /lib.rs crate:foo\n");
+    let krate = db.crate_graph().iter().next().unwrap();
+    let path = Path::from_known_path_with_no_generic(ModPath::from_segments(
+        PathKind::DollarCrate(krate),
+        [name![Option]],
+    ));
+
+    let mut buf = String::new();
+    path.pretty_print(&db, &mut buf, PrettyPrintOpts::default()).unwrap();
+    assert_eq!(buf, "$crate::Option");
+    assert_eq!(path.display_def(&db).to_string(), "foo::Option");
+}
+
+#[test]
+fn replace_segment() {
+    let (db, path) = lower("a::b<u8>::c");
+    let print = |path: Option<Path>| {
+        let mut buf = String::new();
+        print_path(&db, &path.unwrap(), &mut buf).unwrap();
+        buf
+    };
+    let option = name![Option];
+
+    assert!(path.replace_segment(3, |it| PathSegment { name: &option, ..it }).is_none());
+    assert_eq!(
+        print(path.replace_first_segment(|it| PathSegment { name: &option, ..it })),
+        "Option::b<u8>::c"
+    );
+    assert_eq!(
+        print(path.replace_last_segment(|it| PathSegment { name: &option, ..it })),
+        "a::b<u8>::Option"
+    );
+    assert_eq!(
+        print(path.replace_segment(1, |it| PathSegment { name: &option, ..it })),
+        "a::Option<u8>::c"
+    );
+    assert_eq!(
+        print(path.replace_segment(1, |it| PathSegment { args_and_bindings: None, ..it })),
+        "a::b::c"
+    );
+}
+
+#[test]
+fn dyn_trait_paths() {
+    let dyn_paths = |path| {
+        let (_, path) = lower(path);
+        let args = path.segments().last().unwrap().args_and_bindings.unwrap();
+        let paths = args.type_arg_at(0).unwrap().as_dyn_trait_paths()?;
+        Some(paths.map(|it| it.is_dyn_trait_path_component()).collect::<Vec<_>>())
+    };
+    assert_eq!(dyn_paths("Box<dyn Foo>"), Some(vec![true]));
+    assert_eq!(dyn_paths("Box<dyn Foo + Bar + 'a>"), Some(vec![true, true]));
+    assert_eq!(dyn_paths("Box<Foo>"), None);
+    assert_eq!(dyn_paths("Box<&dyn Foo>"), None);
+
+    assert!(!lower("<T as Trait>::Assoc").1.is_dyn_trait_path_component());
+    assert!(!lower("<T>::Assoc").1.is_dyn_trait_path_component());
+}
+
+#[test]
+fn qualified_path_self_type() {
+    let (db, path) = lower("<T as Trait<U>>::Assoc");
+    let args = path.segments().first().unwrap().args_and_bindings.unwrap();
+    let mut buf = String::new();
+    print_type_ref(&db, args.self_type.as_ref().unwrap(), &mut buf).unwrap();
+    assert_eq!(buf, "T");
+    assert_eq!(args.args.len(), 1);
+    assert_eq!(args.named_type_arg_at(0).as_ref().and_then(|it| it.as_str()), Some("U"));
+}
+
+/// Returns the ids of the type and const parameters of the first struct in the crate.
+fn struct_type_or_const_params(db: &TestDB) -> Vec<GenericParamId> {
+    let krate = db.crate_graph().iter().next().unwrap();
+    let def_map = db.crate_def_map(krate);
+    let strukt = def_map[DefMap::ROOT]
+        .scope
+        .declarations()
+        .find_map(|it| match it {
+            ModuleDefId::AdtId(AdtId::StructId(it)) => Some(it),
+            _ => None,
+        })
+        .unwrap();
+    let parent = GenericDefId::AdtId(strukt.into());
+    db.generic_params(parent)
+        .iter()
+        .map(|(local_id, data)| {
+            let id = TypeOrConstParamId { parent, local_id };
+            match data {
+                TypeOrConstParamData::TypeParamData(_) => {
+                    GenericParamId::from(TypeParamId::from_unchecked(id))
+                }
+                TypeOrConstParamData::ConstParamData(_) => {
+                    GenericParamId::from(ConstParamId::from_unchecked(id))
+                }
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn reorder_to_match_params() {
+    let (db, path) = lower_first_path("type T = S<u8, u16, u32>; struct S<Item, Output, Target>;");
+    let params = struct_type_or_const_params(&db);
+    let args = path.segments().last().unwrap().args_and_bindings.unwrap();
+    assert_eq!(args.reorder_to_match_params(&params).as_ref(), Some(args));
+    assert_eq!(args.reorder_to_match_params(&params[..2]), None);
+
+    let (db, path) = lower_first_path("type T = S<3, u8>; struct S<Item, const Index: usize>;");
+    let params = struct_type_or_const_params(&db);
+    let args = path.segments().last().unwrap().args_and_bindings.unwrap();
+    let reordered = args.reorder_to_match_params(&params).unwrap();
+    assert_eq!(reordered.positions_of_type_args().collect::<Vec<_>>(), [0]);
+    assert_eq!(reordered.positions_of_const_args().collect::<Vec<_>>(), [1]);
+}
+
+#[test]
+fn alpha_equivalent() {
+    let check = |a, b| {
+        let mut bindings = FxHashMap::default();
+        if !lower(a).1.alpha_equivalent(&lower(b).1, &mut bindings) {
+            return None;
+        }
+        let mut bindings: Vec<_> = bindings
+            .iter()
+            .map(|(from, to)| (from.as_str().unwrap().to_owned(), to.as_str().unwrap().to_owned()))
+            .collect();
+        bindings.sort();
+        Some(bindings)
+    };
+    let binding = |from: &str, to: &str| (from.to_owned(), to.to_owned());
+
+    assert_eq!(check("Foo<T, T>", "Foo<U, U>"), Some(vec![binding("T", "U")]));
+    assert_eq!(
+        check("Foo<T, &[S]>", "Foo<U, &[V]>"),
+        Some(vec![binding("S", "V"), binding("T", "U")])
+    );
+    assert_eq!(check("Foo<T, T>", "Foo<U, V>"), None);
+    assert_eq!(check("Foo<T, S>", "Foo<U, U>"), None);
+    assert_eq!(check("Foo<T>", "Bar<U>"), None);
+    assert_eq!(check("Foo<T>", "Foo<Vec<U>>"), None);
+}
+
+#[test]
+fn strip_prefix_kind() {
+    let strip = |path, strip: fn(&Path) -> Option<Path>| {
+        let (db, path) = lower(path);
+        let mut buf = String::new();
+        print_path(&db, &strip(&path)?, &mut buf).unwrap();
+        Some(buf)
+    };
+
+    assert_eq!(strip("crate::foo::Bar<u8>", Path::try_strip_crate_prefix).unwrap(), "foo::Bar<u8>");
+    assert_eq!(strip("::foo::Bar", Path::try_strip_crate_prefix), None);
+    assert_eq!(strip("foo::Bar", Path::try_strip_crate_prefix), None);
+    assert_eq!(strip("::foo::Bar", Path::try_strip_abs_prefix).unwrap(), "foo::Bar");
+    assert_eq!(strip("crate::foo::Bar", Path::try_strip_abs_prefix), None);
+    assert_eq!(strip("foo::Bar", Path::try_strip_abs_prefix), None);
+}
+
+#[test]
+fn ordering() {
+    let (db, _) = lower("foo");
+    let mut paths = ["foo::Bar<u8>", "crate::foo", "foo::Bar", "::foo", "foo::Bar<i32>", "foo"]
+        .map(|it| lower(it).1);
+    paths.sort();
+    let printed = paths.iter().map(|it| it.display_def(&db).to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        printed,
+        ["foo", "foo::Bar", "foo::Bar<i32>", "foo::Bar<u8>", "crate::foo", "::foo"]
+    );
+}
+
+#[test]
+fn edition_dependent_path_kinds() {
+    let check = |path, edition, in_import| {
+        let (_, path) = lower(path);
+        (path.is_crate_relative(edition, in_import), path.is_extern_prelude_candidate(edition))
+    };
+
+    assert_eq!(check("crate::foo", Edition::Edition2015, false), (true, false));
+    assert_eq!(check("crate::foo", Edition::Edition2021, true), (true, false));
+    assert_eq!(check("::foo", Edition::Edition2015, false), (true, false));
+    assert_eq!(check("::foo", Edition::Edition2015, true), (true, false));
+    assert_eq!(check("::foo", Edition::Edition2018, false), (false, true));
+    assert_eq!(check("::foo", Edition::Edition2021, true), (false, true));
+    assert_eq!(check("foo::Bar", Edition::Edition2015, false), (false, false));
+    assert_eq!(check("foo::Bar", Edition::Edition2015, true), (true, false));
+    assert_eq!(check("foo::Bar", Edition::Edition2018, false), (false, true));
+    assert_eq!(check("foo::Bar", Edition::Edition2021, true), (false, true));
+    assert_eq!(check("foo", Edition::Edition2024, false), (false, true));
+    assert_eq!(check("self::foo", Edition::Edition2015, true), (false, false));
+    assert_eq!(check("self::foo", Edition::Edition2021, false), (false, false));
+    assert_eq!(check("super::foo", Edition::Edition2015, true), (false, false));
+}
+
+#[test]
+fn error_recovery() {
+    assert!(Path::for_error_recovery(Name::missing()).is_error_recovery());
+    assert!(!lower("Foo").1.is_error_recovery());
+    assert!(!Path::from(name![Option]).is_error_recovery());
+
+    let partial = Path::for_error_recovery(name![Option]);
+    assert_eq!(partial.mod_path().and_then(ModPath::as_ident), Some(&name![Option]));
+}
+
+#[test]
+fn use_item_text() {
+    let (db, path) = lower("crate::foo::Bar");
+    assert_eq!(path.to_use_item_text(&db, None).unwrap(), "use crate::foo::Bar;");
+    assert_eq!(
+        path.to_use_item_text(&db, Some(&ImportAlias::Alias(name![Item]))).unwrap(),
+        "use crate::foo::Bar as Item;"
+    );
+    assert_eq!(
+        path.to_use_item_text(&db, Some(&ImportAlias::Underscore)).unwrap(),
+        "use crate::foo::Bar as _;"
+    );
+    assert_eq!(path.to_glob_use_item_text(&db).unwrap(), "use crate::foo::Bar::*;");
+    assert_eq!(
+        lower("::std::collections").1.to_glob_use_item_text(&db).unwrap(),
+        "use ::std::collections::*;"
+    );
+    assert_eq!(lower("Vec<u8>").1.to_use_item_text(&db, None), None);
+    assert_eq!(lower("<T>::Foo").1.to_use_item_text(&db, None), None);
+}
+
+#[test]
+fn use_group_text() {
+    let (db, _) = lower("Foo");
+    let group = |paths: &[&str]| {
+        let paths: Vec<_> = paths.iter().map(|it| lower(it).1).collect();
+        Path::to_use_group_text(&db, &paths)
+    };
+
+    assert_eq!(
+        group(&["crate::foo::Bar", "crate::foo::baz::Qux", "crate::foo"]).unwrap(),
+        "use crate::foo::{self, Bar, baz::Qux};"
+    );
+    assert_eq!(group(&["crate::foo::Bar"]).unwrap(), "use crate::foo::Bar;");
+    assert_eq!(group(&["crate::foo::Bar", "crate::foo::Bar"]).unwrap(), "use crate::foo::Bar;");
+    assert_eq!(group(&["foo::Bar", "foo", "foo::Baz"]).unwrap(), "use foo::{self, Bar, Baz};");
+    assert_eq!(group(&["foo::Bar", "baz::Qux"]).unwrap(), "use {foo::Bar, baz::Qux};");
+    assert_eq!(group(&["::foo::Bar", "::baz::Qux"]).unwrap(), "use ::{foo::Bar, baz::Qux};");
+    assert_eq!(group(&["crate::foo::Bar", "super::Qux"]), None);
+    assert_eq!(group(&[]), None);
+}
+
+#[test]
+fn lifetimes_erased() {
+    let check = |path, expect: Expect| {
+        let (db, path) = lower(path);
+        let mut buf = String::new();
+        print_path(&db, &path.with_lifetimes_erased(), &mut buf).unwrap();
+        expect.assert_eq(&buf);
+    };
+
+    check("HashMap<'a, K, V>", expect![[r#"HashMap<K, V>"#]]);
+    check("Foo<&'a T, &'static mut U>", expect![[r#"Foo<&T, &mut U>"#]]);
+    check("Foo<Bar<'a>, dyn Baz + 'a>", expect![[r#"Foo<Bar, dyn Baz>"#]]);
+    check("Iterator<Item = &'a T>", expect![[r#"Iterator<Item = &T>"#]]);
+
+    assert_eq!(lower("Foo<'a>").1.with_lifetimes_erased(), lower("Foo").1);
+}
+
+#[test]
+fn rewrite_all_paths() {
+    let (db, ty) = {
+        let (db, file_id) =
+            TestDB::with_single_file("type T = (Foo<Bar>, &[Foo<Baz>], dyn Tr<Foo>);");
+        let file = db.parse(file_id).tree();
+        let ast_ty = file.syntax().descendants().find_map(ast::Type::cast).unwrap();
+        let ty = {
+            let ctx = LowerCtx::new(&db, file_id.into());
+            TypeRef::from_ast(&ctx, ast_ty)
+        };
+        (db, ty)
+    };
+    let (_, qux) = lower("Qux");
+
+    let is_foo =
+        |path: &Path| path.segments().last().and_then(|it| it.name.as_str()) == Some("Foo");
+
+    let rewritten = rewrite::rewrite_all_paths(&ty, |path| {
+        (is_foo(path) && path.segments().last()?.args_and_bindings.is_none()).then(|| qux.clone())
+    });
+    let mut buf = String::new();
+    print_type_ref(&db, &rewritten, &mut buf).unwrap();
+    expect![[r#"(Foo<Bar>, &[Foo<Baz>], dyn Tr<Qux>)"#]].assert_eq(&buf);
+
+    let rewritten = rewrite::rewrite_all_paths(&ty, |path| is_foo(path).then(|| qux.clone()));
+    let mut buf = String::new();
+    print_type_ref(&db, &rewritten, &mut buf).unwrap();
+    expect![[r#"(Qux, &[Qux], dyn Tr<Qux>)"#]].assert_eq(&buf);
+
+    assert_eq!(rewrite::rewrite_all_paths(&ty, |_| None), ty);
+}
+
+#[test]
+fn ancestors() {
+    let check = |path, expect: Expect| {
+        let (db, path) = lower(path);
+        let mut buf = String::new();
+        for ancestor in path.ancestors() {
+            print_path(&db, &ancestor, &mut buf).unwrap();
+            buf.push('\n');
+        }
+        expect.assert_eq(&buf);
+    };
+
+    check(
+        "foo",
+        expect![[r#"
+            foo
+        "#]],
+    );
+    check(
+        "a::b::C<T>",
+        expect![[r#"
+            a::b::C<T>
+            a::b
+            a
+        "#]],
+    );
+    check(
+        "crate::foo::Bar",
+        expect![[r#"
+            crate::foo::Bar
+            crate::foo
+        "#]],
+    );
+    check(
+        "self",
+        expect![[r#"
+            self
+        "#]],
+    );
+}
+
+#[test]
+fn from_fully_qualified() {
+    let (db, vec) = lower("::std::vec::Vec");
+    assert_eq!(Path::from_fully_qualified(&name![std], [name![vec], name![Vec]]), vec);
+    assert_eq!(Path::from_fully_qualified_in_std([name![vec], name![Vec]]), vec);
+
+    let option = Path::from_fully_qualified_in_core([name![option], name![Option]]);
+    let mut buf = String::new();
+    print_path(&db, &option, &mut buf).unwrap();
+    expect![[r#"::core::option::Option"#]].assert_eq(&buf);
+}
+
+#[test]
+fn std_crate_of() {
+    let std_crate_of = |path| lower(path).1.std_crate_of();
+
+    assert_eq!(std_crate_of("::std::vec::Vec"), Some(StdCrate::Std));
+    assert_eq!(std_crate_of("::core::option::Option"), Some(StdCrate::Core));
+    assert_eq!(std_crate_of("::alloc::vec::Vec"), Some(StdCrate::Alloc));
+    assert_eq!(std_crate_of("::serde::Serialize"), None);
+    assert_eq!(std_crate_of("std::vec::Vec"), None);
+    assert_eq!(std_crate_of("crate::std::Vec"), None);
+
+    assert!(lower("::core::mem::swap").1.is_in_std_crate());
+    assert!(!lower("::foo::bar").1.is_in_std_crate());
+}
+
+#[test]
+fn type_arg_names() {
+    let (_, path) = lower("Foo<'a, T, Vec<u8>, U, 1>");
+    let args = path.segments().last().unwrap().args_and_bindings.unwrap();
+    let names = args.type_arg_names().unwrap();
+    let names: Vec<_> = names.iter().map(|it| it.as_ref().and_then(|it| it.as_str())).collect();
+    assert_eq!(names, [Some("T"), None, Some("U")]);
+
+    assert_eq!(args.named_type_arg_at(2).as_ref().and_then(|it| it.as_str()), Some("U"));
+    assert_eq!(args.named_type_arg_at(1), None);
+    assert_eq!(args.named_type_arg_at(3), None);
+
+    let (_, path) = lower("Foo<'a>");
+    let args = path.segments().last().unwrap().args_and_bindings.unwrap();
+    assert_eq!(args.type_arg_names(), None);
+}
+
+#[test]
+fn path_builder() {
+    let (db, ty) = lower("T");
+    let check = |path: Path, expect: Expect| {
+        let mut buf = String::new();
+        print_path(&db, &path, &mut buf).unwrap();
+        expect.assert_eq(&buf);
+    };
+
+    check(
+        PathBuilder::new(PathKind::Abs).segment(name![core]).segment(name![option]).finish(),
+        expect![[r#"::core::option"#]],
+    );
+    check(
+        PathBuilder::new(PathKind::Abs)
+            .kind(PathKind::Crate)
+            .segment(name![option])
+            .segment_with_args(name![Option], [GenericArg::Type(TypeRef::unit())])
+            .finish(),
+        expect![[r#"crate::option::Option<()>"#]],
+    );
+    check(
+        PathBuilder::new(PathKind::Plain)
+            .segment(name![future])
+            .segment(name![Future])
+            .binding(name![Output], TypeRef::Path(ty.clone()))
+            .finish(),
+        expect![[r#"future::Future<Output = T>"#]],
+    );
+    check(
+        PathBuilder::new(PathKind::Plain)
+            .segment_with_args(name![Iterator], [])
+            .binding(name![Item], TypeRef::Path(ty))
+            .finish(),
+        expect![[r#"Iterator<Item = T>"#]],
+    );
+
+    assert_eq!(PathBuilder::new(PathKind::Plain).segment(name![Vec]).finish(), lower("Vec").1);
+}
+
+#[test]
+fn generic_args_constructors() {
+    let generic_args = |path| {
+        let (_, path) = lower(path);
+        path.segments().last().unwrap().args_and_bindings.unwrap().clone()
+    };
+    let ty = |path| TypeRef::Path(lower(path).1);
+
+    let args = GenericArgs::from_types([ty("A"), ty("B")]);
+    assert_eq!(args, generic_args("Foo<A, B>"));
+    let args = args.with_binding(name![Item], ty("C"));
+    assert_eq!(args, generic_args("Foo<A, B, Item = C>"));
+
+    let merged = GenericArgs::from_types([ty("A")])
+        .merge(&GenericArgs::from_types([ty("B")]).with_binding(name![Item], ty("C")));
+    assert_eq!(merged, args);
+
+    let (_, path) = lower("<T as Trait<A>>::Foo");
+    let self_args = GenericArgs { self_type: Some(ty("T")), ..GenericArgs::empty() };
+    assert_eq!(
+        path.segments().first().unwrap().args_and_bindings.unwrap(),
+        &self_args.merge(&GenericArgs::from_types([ty("A")]))
+    );
+
+    let (_, path) = lower("<T as Fn(A)>::Output");
+    let merged = self_args.merge(&generic_args("Fn(A)"));
+    assert!(merged.desugared_from_fn);
+    assert_eq!(path.segments().first().unwrap().args_and_bindings.unwrap(), &merged);
+    let merged = generic_args("Fn(A)").merge(&self_args);
+    assert!(merged.desugared_from_fn && merged.self_type.is_none());
+}
+
+#[test]
+fn display_qualified_paths() {
+    let check = |path, expect: Expect| {
+        let (db, path) = lower(path);
+        expect.assert_eq(&path.display_def(&db).to_string());
+    };
+
+    check("<T as Trait>::Assoc", expect![[r#"<T as Trait>::Assoc"#]]);
+    check("<T as Trait<A>>::foo::<B>", expect![[r#"<T as Trait<A>>::foo::<B>"#]]);
+    check(
+        "<Vec<T> as crate::Trait<Item = u8>>::Assoc",
+        expect![[r#"<Vec<T> as crate::Trait<Item = u8>>::Assoc"#]],
+    );
+    check("<T>::Assoc", expect![[r#"<T>::Assoc"#]]);
+
+    let (db, path) = lower("<T as Trait>::Assoc");
+    let mut buf = String::new();
+    print_path(&db, &path, &mut buf).unwrap();
+    expect![[r#"Trait<Self=T>::Assoc"#]].assert_eq(&buf);
+}
+
+#[test]
+fn nested_qualified_paths() {
+    check(
+        "<Vec<u8> as IntoIterator>::IntoIter::Item",
+        expect![[r#"IntoIterator<Self=Vec<u8>>::IntoIter::Item"#]],
+    );
+    check(
+        "<T as a::Trait<A>>::Assoc<B>::Nested",
+        expect![[r#"a::Trait<Self=T, A>::Assoc<B>::Nested"#]],
+    );
+
+    let (db, path) = lower("<Vec<u8> as IntoIterator>::IntoIter::Item");
+    assert_eq!(path.segments().len(), 3);
+    assert_eq!(path.display_def(&db).to_string(), "<Vec<u8> as IntoIterator>::IntoIter::Item");
+    assert_eq!(
+        path.qualifier().unwrap().display_def(&db).to_string(),
+        "<Vec<u8> as IntoIterator>::IntoIter"
+    );
+
+    let (db, path) = lower("<T as a::Trait<A>>::Assoc<B>::Nested");
+    assert_eq!(path.display_def(&db).to_string(), "<T as a::Trait<A>>::Assoc<B>::Nested");
+}
+
+#[test]
+fn to_ast_round_trip() {
+    let check = |path, expect: Expect| {
+        let (db, file_id) = TestDB::with_single_file(&format!("type T = {path};"));
+        let ctx = LowerCtx::new(&db, file_id.into());
+        let file = db.parse(file_id).tree();
+        let path =
+            Path::from_src(&ctx, file.syntax().descendants().find_map(ast::Path::cast).unwrap())
+                .unwrap();
+
+        let ast_path = path.to_ast(&db).unwrap();
+        expect.assert_eq(&ast_path.syntax().to_string());
+        assert_eq!(Path::from_src(&ctx, ast_path).unwrap(), path);
+    };
+
+    check("foo::Bar", expect![[r#"foo::Bar"#]]);
+    check("::std::vec::Vec<u8>", expect![[r#"::std::vec::Vec<u8>"#]]);
+    check("super::super::Foo<'a, T, 1>", expect![[r#"super::super::Foo<'a, T, 1>"#]]);
+    check("crate::Iterator<Item = &'a [T]>", expect![[r#"crate::Iterator<Item = &'a [T]>"#]]);
+    check("<T as Trait<A>>::Assoc", expect![[r#"<T as Trait<A>>::Assoc"#]]);
+    check("<[T]>::len", expect![[r#"<[T]>::len"#]]);
+    check("Option::<Vec<u8>>", expect![[r#"Option::<Vec<u8>>"#]]);
+
+    let (db, _) = lower("foo");
+    assert_eq!(Path::for_error_recovery(Name::missing()).to_ast(&db), None);
+}
+
+#[test]
+fn parse() {
+    let (db, try_) = lower("std::ops::Try");
+    assert_eq!(Path::parse(&db, "std::ops::Try"), Ok(try_));
+    let (_, item) = lower("<T as Iterator<Item = u8>>::Item");
+    assert_eq!(Path::parse(&db, "<T as Iterator<Item = u8>>::Item"), Ok(item));
+
+    assert_eq!(Path::parse(&db, "&T"), Err(PathParseError::NotAPath));
+    assert_eq!(Path::parse(&db, "foo bar"), Err(PathParseError::NotAPath));
+    assert!(matches!(
+        Path::parse(&db, "foo::Bar<T,,>::baz"),
+        Err(PathParseError::InvalidSegment { index: 1, .. })
+    ));
+    let (_, literal_arg) = lower("foo::Bar<1>::baz");
+    assert_eq!(Path::parse(&db, "foo::Bar<1>::baz"), Ok(literal_arg));
+    assert_eq!(
+        Path::parse(&db, "foo::Bar<{ 1 }>::baz"),
+        Err(PathParseError::UnsupportedSegment { index: 1, segment: "Bar<{ 1 }>".to_owned() })
+    );
+}
+
+#[test]
+fn split_last() {
+    let (_, path) = lower("crate::foo::Bar<T>");
+    let (qualifier, last) = path.split_last().unwrap();
+    assert_eq!(qualifier.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(), [Some("foo")]);
+    assert!(qualifier.iter().all(|it| it.args_and_bindings.is_none()));
+    assert_eq!(last.name.as_str(), Some("Bar"));
+    assert!(last.args_and_bindings.is_some());
+
+    let (_, path) = lower("Foo");
+    let (qualifier, last) = path.split_last().unwrap();
+    assert!(qualifier.is_empty());
+    assert_eq!(last.name.as_str(), Some("Foo"));
+
+    let (_, path) = lower("self");
+    assert!(path.split_last().is_none());
+}
+
+#[test]
+fn strip_prefix() {
+    let (db, path) = lower("std::ops::Add<u8>::Output");
+    let (_, std_ops) = lower("std::ops");
+
+    assert!(path.starts_with(&std_ops));
+    assert!(path.starts_with(&path));
+    assert!(path.starts_with(&lower("std::ops::Add<u16>").1));
+    assert!(!path.starts_with(&lower("::std::ops").1));
+    assert!(!path.starts_with(&lower("std::mem").1));
+    assert!(!path.starts_with(&lower("<T>::std").1));
+    assert!(!std_ops.starts_with(&path));
+
+    let mut buf = String::new();
+    print_path(&db, &path.strip_prefix(&std_ops).unwrap(), &mut buf).unwrap();
+    expect![[r#"Add<u8>::Output"#]].assert_eq(&buf);
+    assert_eq!(path.strip_prefix(&lower("std::ops::Add").1), Some(lower("Output").1));
+    assert_eq!(path.strip_prefix(&path), None);
+    assert_eq!(path.strip_prefix(&lower("core::ops").1), None);
+}
+
+#[test]
+fn join() {
+    let check = |path: &str, other: &str, expect: Expect| {
+        let (db, path) = lower(path);
+        let joined = path.join(lower(other).1);
+        let mut buf = String::new();
+        if let Some(joined) = &joined {
+            print_path(&db, joined, &mut buf).unwrap();
+        }
+        expect.assert_eq(&buf);
+    };
+
+    check("foo::bar", "baz::Qux<T>", expect![[r#"foo::bar::baz::Qux<T>"#]]);
+    check("crate::Foo<T>", "Bar", expect![[r#"crate::Foo<T>::Bar"#]]);
+    check("<T>::Foo", "bar", expect![[r#"<T>::Foo::bar"#]]);
+    check("foo", "::bar", expect![[r#""#]]);
+    check("foo", "self::bar", expect![[r#""#]]);
+    check("foo", "crate::bar", expect![[r#""#]]);
+    check("foo", "<T>::bar", expect![[r#""#]]);
+
+    assert_eq!(lower("foo::bar").1.join(lower("baz").1), Some(lower("foo::bar::baz").1));
+
+    let (_, mut path) = lower("foo::Bar<T>");
+    path.push_segment(name![Output]);
+    assert_eq!(path, lower("foo::Bar<T>::Output").1);
+    let (_, mut path) = lower("crate::foo");
+    path.push_segment(name![Output]);
+    assert_eq!(path, lower("crate::foo::Output").1);
+}
+
+#[test]
+fn mutators() {
+    let (_, mut path) = lower("foo::Bar<T>::Output");
+    assert_eq!(path.pop_segment(), Some(name![Output]));
+    assert_eq!(path, lower("foo::Bar<T>").1);
+    assert_eq!(path.pop_segment().unwrap().as_str(), Some("Bar"));
+    assert_eq!(path, lower("foo").1);
+    assert!(path.pop_segment().is_some());
+    assert_eq!(path.pop_segment(), None);
+
+    let (_, mut path) = lower("foo::Bar<T>");
+    let (_, vec) = lower("Vec<u8>");
+    path.set_segment(1, vec.segments().last().unwrap());
+    assert_eq!(path, lower("foo::Vec<u8>").1);
+    path.set_segment(0, vec.segments().last().unwrap());
+    assert_eq!(path, lower("Vec<u8>::Vec<u8>").1);
+
+    let (_, mut path) = lower("foo::Bar");
+    path.set_kind(PathKind::Crate);
+    assert_eq!(path, lower("crate::foo::Bar").1);
+    path.set_kind(PathKind::Super(2));
+    assert_eq!(path, lower("super::super::foo::Bar").1);
+
+    let (_, mut path) = lower("<T>::Bar");
+    path.set_kind(PathKind::Plain);
+    assert_eq!(path, lower("<T>::Bar").1);
+}
+
+#[test]
+fn raw_identifiers() {
+    let (db, path) = lower("r#type::r#fn::r#Foo");
+    expect![[r#"r#type::r#fn::Foo"#]].assert_eq(&path.display_def(&db).to_string());
+    assert_eq!(path.to_ast(&db).unwrap().syntax().to_string(), "r#type::r#fn::Foo");
+
+    let segments = path.segments();
+    let first = segments.first().unwrap();
+    assert!(first.name.is_escaped());
+    assert_eq!(first.name.unescaped().to_smol_str(), "type");
+    assert!(!segments.last().unwrap().name.is_escaped());
+}
+
+#[test]
+fn incomplete_paths() {
+    let (db, file_id) = TestDB::with_single_file("fn f() { foo::bar:: }");
+    let file = db.parse(file_id).tree();
+    let ast_path = file.syntax().descendants().find_map(ast::Path::cast).unwrap();
+    let ctx = LowerCtx::new(&db, file_id.into());
+    assert_eq!(Path::from_src(&ctx, ast_path), None);
+
+    let (_, path) = lower_first_path("fn f() { crate::foo::<; }");
+    assert_eq!(path, lower("crate::foo").1);
+}
+
+#[test]
+fn lowering_errors() {
+    let check = |ra_fixture: &str, expected: Result<(), PathLoweringError>| {
+        let (db, file_id) = TestDB::with_single_file(ra_fixture);
+        let file = db.parse(file_id).tree();
+        let ast_path = file.syntax().descendants().find_map(ast::Path::cast).unwrap();
+        let ctx = LowerCtx::new(&db, file_id.into());
+        assert_eq!(Path::try_from_src(&ctx, ast_path).map(drop), expected);
+    };
+
+    check("type T = foo::Bar;", Ok(()));
+    check("type T = foo::;", Err(PathLoweringError::MissingSegment));
+    check("type T = <T as Trait>::Assoc;", Ok(()));
+    check("type T = <>::Assoc;", Err(PathLoweringError::MissingSelfType));
+    check("type T = <T as <>::Trait>::Assoc;", Err(PathLoweringError::InvalidQualifiedTrait));
+    check("type T = <T as>::Assoc;", Err(PathLoweringError::InvalidQualifiedTrait));
+}
+
+#[test]
+fn eq_ignoring_args() {
+    let hash = |path: &Path| {
+        let mut hasher = FxHasher::default();
+        path.hash_ignoring_args(&mut hasher);
+        hasher.finish()
+    };
+    let check = |a: &str, b: &str, expected: bool| {
+        let (a, b) = (lower(a).1, lower(b).1);
+        assert_eq!(a.eq_ignoring_args(&b), expected);
+        if expected {
+            assert_eq!(hash(&a), hash(&b));
+        }
+    };
+
+    check("foo::Bar<T>", "foo::Bar<U>", true);
+    check("foo<'a>::Bar", "foo::Bar<u8, Item = ()>", true);
+    check("<T>::Bar", "<U>::Bar", true);
+    check("crate::Bar", "crate::Bar", true);
+    check("foo::Bar<T>", "foo::Baz<T>", false);
+    check("foo::Bar", "::foo::Bar", false);
+    check("foo::Bar", "foo", false);
+}
+
+#[test]
+fn walk_type_refs() {
+    let (db, mut path) = lower("<Vec<Self> as Tr<Item = (A, Self)>>::Foo<&Self, Bar: Baz<Self>>");
+    let mut types = String::new();
+    path.walk_type_refs(&mut |type_ref| {
+        print_type_ref(&db, type_ref, &mut types).unwrap();
+        types.push('\n');
+    });
+    expect![[r#"
+        Vec<Self>
+        Self
+        (A, Self)
+        A
+        Self
+        &Self
+        Self
+        Self
+    "#]]
+    .assert_eq(&types);
+
+    let (_, t) = lower("T");
+    path.walk_type_refs_mut(&mut |type_ref| {
+        if matches!(type_ref, TypeRef::Path(path) if path.is_self_type()) {
+            *type_ref = TypeRef::Path(t.clone());
+        }
+    });
+    assert_eq!(path, lower("<Vec<T> as Tr<Item = (A, T)>>::Foo<&T, Bar: Baz<T>>").1);
+}
+
+#[test]
+fn std_crate_for_def_map() {
+    let check = |ra_fixture: &str, expected: StdCrate| {
+        let (db, _) = TestDB::with_single_file(ra_fixture);
+        let krate = db.crate_graph().iter().next().unwrap();
+        let std_crate = StdCrate::for_def_map(&db.crate_def_map(krate));
+        assert_eq!(std_crate, expected);
+        assert_eq!(
+            Path::from_fully_qualified(&std_crate.name(), [name![option], name![Option]])
+                .std_crate_of(),
+            Some(expected)
+        );
+    };
+
+    check("struct S;", StdCrate::Std);
+    check("#![no_std]\nstruct S;", StdCrate::Core);
+}
+
+#[test]
+fn canonicalize_in() {
+    let (db, _) = TestDB::with_single_file(
+        r#"
+mod a {
+    mod b {}
+}
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let def_map = db.crate_def_map(krate);
+    let b = def_map
+        .modules()
+        .find(|(_, data)| data.children.is_empty() && data.parent.is_some())
+        .map(|(local_id, _)| def_map.module_id(local_id))
+        .unwrap();
+    assert_eq!(b.name(&db).unwrap().as_str(), Some("b"));
+
+    let check = |path, expect: Expect| {
+        let canonical = lower(path).1.canonicalize_in(&db, b);
+        let mut buf = String::new();
+        if let Some(canonical) = &canonical {
+            print_path(&db, canonical, &mut buf).unwrap();
+        }
+        expect.assert_eq(&buf);
+    };
+
+    check("self::Foo<T>", expect![[r#"crate::a::b::Foo<T>"#]]);
+    check("super::Foo", expect![[r#"crate::a::Foo"#]]);
+    check("super::super::foo::Bar", expect![[r#"crate::foo::Bar"#]]);
+    check("super::super::super::Foo", expect![[r#""#]]);
+    check("crate::Foo", expect![[r#"crate::Foo"#]]);
+    check("Foo", expect![[r#"Foo"#]]);
+
+    let root = def_map.crate_root().into();
+    assert_eq!(lower("self::Foo").1.canonicalize_in(&db, root), Some(lower("crate::Foo").1));
+}
+
+#[test]
+fn resolve_dollar_crate() {
+    let db = TestDB::with_files(
+        r#"
+//- /main.rs crate:main deps:lib
+//- /lib.rs crate:lib deps:inner
+//- /inner.rs crate:inner
+"#,
+    );
+    let krate = |name| crate_by_name(&db, name);
+    let resolve = |path_crate, local_crate| {
+        let path = PathBuilder::new(PathKind::DollarCrate(krate(path_crate)))
+            .segment(name![option])
+            .segment_with_args(name![Option], [GenericArg::Type(TypeRef::unit())])
+            .finish();
+        let path = path.resolve_dollar_crate(&db, krate(local_crate))?;
+        let mut buf = String::new();
+        print_path(&db, &path, &mut buf).unwrap();
+        Some(buf)
+    };
+
+    assert_eq!(resolve("main", "main").as_deref(), Some("crate::option::Option<()>"));
+    assert_eq!(resolve("lib", "main").as_deref(), Some("::lib::option::Option<()>"));
+    // `inner` is not a dependency of `main`, e.g. a macro of `inner` re-exported by `lib`.
+    assert_eq!(resolve("inner", "main"), None);
+    assert_eq!(resolve("inner", "lib").as_deref(), Some("::inner::option::Option<()>"));
+
+    let path = lower("crate::Foo").1;
+    assert_eq!(path.resolve_dollar_crate(&db, krate("main")), Some(path));
+}
+
+#[test]
+fn display_with_crate_prefix() {
+    let db = TestDB::with_files(
+        r#"
+//- /main.rs crate:main deps:lib
+//- /lib.rs crate:lib
+"#,
+    );
+    let (main, lib) = (crate_by_name(&db, "main"), crate_by_name(&db, "lib"));
+
+    let path = PathBuilder::new(PathKind::DollarCrate(lib)).segment(name![Option]).finish();
+    assert_eq!(path.display_with(&db, CratePrefix::RelativeTo(main)).to_string(), "lib::Option");
+    assert_eq!(path.display_with(&db, CratePrefix::RelativeTo(lib)).to_string(), "crate::Option");
+    assert_eq!(path.display_with(&db, CratePrefix::Unchanged).to_string(), "lib::Option");
+    // `lib` can't name `main`, so the `$crate` is kept.
+    let path = PathBuilder::new(PathKind::DollarCrate(main)).segment(name![Option]).finish();
+    assert_eq!(path.display_with(&db, CratePrefix::RelativeTo(lib)).to_string(), "$crate::Option");
+
+    let path = lower("::lib::Option<u8>").1;
+    assert_eq!(
+        path.display_with(&db, CratePrefix::RelativeTo(main)).to_string(),
+        "lib::Option<u8>"
+    );
+    assert_eq!(path.display_with(&db, CratePrefix::Unchanged).to_string(), "::lib::Option<u8>");
+}
+
+#[test]
+fn stable_hash() {
+    let (db, path) = lower("crate::foo::Bar<T, Item = u8>");
+    let (other_db, same_path) = lower("crate :: foo :: Bar < T , Item = u8 >");
+    assert_eq!(path.stable_hash(&db), same_path.stable_hash(&other_db));
+    // The hash only depends on the path's text and a fixed algorithm, so it can be hardcoded.
+    assert_eq!(path.stable_hash(&db), 16190738998401321893);
+
+    assert_ne!(path.stable_hash(&db), lower("crate::foo::Bar<U, Item = u8>").1.stable_hash(&db));
+    assert_ne!(path.stable_hash(&db), lower("foo::Bar<T, Item = u8>").1.stable_hash(&db));
+
+    let db = TestDB::with_files(
+        r#"
+//- /main.rs crate:main deps:lib
+//- /lib.rs crate:lib
+"#,
+    );
+    let (main, lib) = (crate_by_name(&db, "main"), crate_by_name(&db, "lib"));
+    let dollar_crate =
+        |krate| PathBuilder::new(PathKind::DollarCrate(krate)).segment(name![Option]).finish();
+    assert_eq!(dollar_crate(main).stable_hash(&db), dollar_crate(lib).stable_hash(&db));
+}
+
+#[test]
+fn stable_hash_lang_item() {
+    let lang_item_path = |fixture: &str| {
+        let (db, file_id) = TestDB::with_single_file(fixture);
+        let krate = db.module_for_file(file_id).krate();
+        let target = db.lang_item(krate, LangItem::OwnedBox).unwrap();
+        let hash = Path::LangItem(target, Some(name![new])).stable_hash(&db);
+        (target, hash)
+    };
+    let (target, hash) = lang_item_path(
+        r#"
+#[lang = "owned_box"]
+struct Box;
+"#,
+    );
+    let (other_target, other_hash) = lang_item_path(
+        r#"
+struct Before;
+enum AlsoBefore {}
+#[lang = "owned_box"]
+struct Box;
+"#,
+    );
+    // The items have different ids, but the hash only depends on the lang item's name.
+    assert_ne!(target, other_target);
+    assert_eq!(hash, other_hash);
+}
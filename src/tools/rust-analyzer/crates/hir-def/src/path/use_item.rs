@@ -0,0 +1,74 @@
+//! Rendering of paths as `use` items, for assists that insert imports.
+
+use itertools::Itertools;
+
+use crate::{
+    db::DefDatabase,
+    path::{ImportAlias, ModPath, Path, PathKind},
+};
+
+impl Path {
+    /// Renders an import of this path, e.g. `use crate::foo::Bar as Baz;`. Returns `None` if the
+    /// path can't be imported because it has generic args or a type anchor.
+    pub fn to_use_item_text(
+        &self,
+        db: &dyn DefDatabase,
+        alias: Option<&ImportAlias>,
+    ) -> Option<String> {
+        let path = importable_mod_path(self)?.display(db.upcast());
+        Some(match alias {
+            Some(alias) => format!("use {path} as {alias};"),
+            None => format!("use {path};"),
+        })
+    }
+
+    /// Renders a glob import of this path, e.g. `use crate::foo::*;`.
+    pub fn to_glob_use_item_text(&self, db: &dyn DefDatabase) -> Option<String> {
+        let path = importable_mod_path(self)?;
+        if path.segments().is_empty() && path.kind == PathKind::Plain {
+            return None;
+        }
+        Some(format!("use {}::*;", path.display(db.upcast())))
+    }
+
+    /// Renders a single `use` item importing all of `paths`, grouped by their longest common
+    /// prefix, e.g. `use crate::foo::{self, Bar, baz::Qux};`. `self` is listed first and a single
+    /// path is imported without braces. All paths need to be of the same [`PathKind`].
+    pub fn to_use_group_text(db: &dyn DefDatabase, paths: &[Path]) -> Option<String> {
+        let paths = paths.iter().map(importable_mod_path).unique().collect::<Option<Vec<_>>>()?;
+        let (first, rest) = paths.split_first()?;
+        if rest.is_empty() {
+            return Some(format!("use {};", first.display(db.upcast())));
+        }
+        if rest.iter().any(|it| it.kind != first.kind) {
+            return None;
+        }
+        let prefix_len = rest.iter().fold(first.segments().len(), |len, path| {
+            first.segments()[..len].iter().zip(path.segments()).take_while(|(a, b)| a == b).count()
+        });
+
+        let prefix =
+            ModPath::from_segments(first.kind, first.segments()[..prefix_len].iter().cloned());
+        let items = paths
+            .iter()
+            .map(|path| &path.segments()[prefix_len..])
+            .sorted_by_key(|rest| !rest.is_empty())
+            .map(|rest| match rest {
+                [] => "self".to_owned(),
+                rest => rest.iter().map(|it| it.display(db.upcast())).join("::"),
+            })
+            .join(", ");
+        Some(if prefix.kind == PathKind::Plain && prefix.segments().is_empty() {
+            format!("use {{{items}}};")
+        } else {
+            format!("use {}::{{{items}}};", prefix.display(db.upcast()))
+        })
+    }
+}
+
+fn importable_mod_path(path: &Path) -> Option<&ModPath> {
+    match path {
+        Path::Normal { type_anchor: None, mod_path, generic_args: None } => Some(mod_path),
+        _ => None,
+    }
+}
@@ -2,18 +2,29 @@
 
 use std::fmt::{self, Write};
 
-use hir_expand::mod_path::PathKind;
+use hir_expand::{mod_path::PathKind, name::name};
 use intern::Interned;
 use itertools::Itertools;
 
 use crate::{
     db::DefDatabase,
     lang_item::LangItemTarget,
-    path::{GenericArg, GenericArgs, Path},
+    path::{GenericArg, GenericArgs, Path, PathSegment, PrettyPrintOpts},
     type_ref::{Mutability, TraitBoundModifier, TypeBound, TypeRef},
 };
 
 pub(crate) fn print_path(db: &dyn DefDatabase, path: &Path, buf: &mut dyn Write) -> fmt::Result {
+    print_path_with_opts(db, path, buf, PrettyPrintOpts::default())
+}
+
+/// Prints `path` according to `opts`. Note that the options only apply to `path` itself, paths
+/// nested in its generic args are printed with the default options.
+pub(crate) fn print_path_with_opts(
+    db: &dyn DefDatabase,
+    path: &Path,
+    buf: &mut dyn Write,
+    opts: PrettyPrintOpts,
+) -> fmt::Result {
     if let Path::LangItem(it, s) = path {
         write!(buf, "builtin#lang(")?;
         match *it {
@@ -49,44 +60,116 @@ pub(crate) fn print_path(db: &dyn DefDatabase, path: &Path, buf: &mut dyn Write)
         }
         return write!(buf, ")");
     }
+
+    if opts.resugar_qualified_paths && path.type_anchor().is_none() {
+        let qualified_segment = path
+            .segments()
+            .iter()
+            .position(|segment| segment.args_and_bindings.is_some_and(|it| it.self_type.is_some()));
+        if let Some(idx) = qualified_segment {
+            return print_qualified_path(db, path, idx, buf, opts);
+        }
+    }
+
+    let mut kind = *path.kind();
+    let mut segments = path.segments();
+    if opts.use_short_std
+        && path.type_anchor().is_none()
+        && matches!(kind, PathKind::Plain | PathKind::Abs)
+        && segments.len() > 1
+        && segments.first().is_some_and(|it| *it.name == name![std])
+    {
+        kind = PathKind::Plain;
+        segments = segments.skip(1);
+    }
+
     match path.type_anchor() {
         Some(anchor) => {
             write!(buf, "<")?;
             print_type_ref(db, anchor, buf)?;
             write!(buf, ">::")?;
         }
-        None => match path.kind() {
-            PathKind::Plain => {}
-            PathKind::Super(0) => write!(buf, "self")?,
-            PathKind::Super(n) => {
-                for i in 0..*n {
-                    if i == 0 {
-                        buf.write_str("super")?;
-                    } else {
-                        buf.write_str("::super")?;
-                    }
+        None => {
+            let mut leading = String::new();
+            match kind {
+                _ if kind.has_leading_separator() && !opts.show_leading_separator => {}
+                PathKind::DollarCrate(krate) if opts.resolve_dollar_crate => {
+                    let crate_graph = db.crate_graph();
+                    let name = crate_graph[krate]
+                        .display_name
+                        .as_ref()
+                        .map_or("$crate", |name| name.canonical_name());
+                    write!(leading, "{name}::")?
                 }
+                _ => kind.fmt_leading(&mut leading)?,
             }
-            PathKind::Crate => write!(buf, "crate")?,
-            PathKind::Abs => {}
-            PathKind::DollarCrate(_) => write!(buf, "$crate")?,
-        },
+            // A path consisting of only `self`, `super` or `crate` has no trailing separator.
+            let leading = match segments.is_empty() {
+                true => leading.strip_suffix("::").unwrap_or(&leading),
+                false => &leading,
+            };
+            buf.write_str(leading)?;
+        }
     }
 
-    for (i, segment) in path.segments().iter().enumerate() {
-        if i != 0 || !matches!(path.kind(), PathKind::Plain) {
+    for (i, segment) in segments.iter().enumerate() {
+        if i != 0 {
             write!(buf, "::")?;
         }
+        print_path_segment(db, segment, buf, opts)?;
+    }
 
-        write!(buf, "{}", segment.name.display(db.upcast()))?;
-        if let Some(generics) = segment.args_and_bindings {
-            write!(buf, "::<")?;
-            print_generic_args(db, generics, buf)?;
+    Ok(())
+}
 
-            write!(buf, ">")?;
-        }
+/// Prints a path desugared from `<Type as Trait>::foo` (see [`Path::Normal`]) in its original
+/// form, with `idx` being the index of the trait's last segment.
+fn print_qualified_path(
+    db: &dyn DefDatabase,
+    path: &Path,
+    idx: usize,
+    buf: &mut dyn Write,
+    opts: PrettyPrintOpts,
+) -> fmt::Result {
+    let segments = path.segments();
+    let generics = segments.get(idx).and_then(|it| it.args_and_bindings).unwrap();
+    let self_ty = generics.self_type.as_ref().unwrap();
+    let trait_args = GenericArgs { self_type: None, ..generics.clone() };
+    let has_trait_args = !trait_args.args.is_empty() || !trait_args.bindings.is_empty();
+    let trait_path = path.ancestors().nth(segments.len() - 1 - idx).and_then(|it| {
+        it.replace_last_segment(|segment| PathSegment {
+            name: segment.name,
+            args_and_bindings: has_trait_args.then_some(&trait_args),
+        })
+    });
+
+    write!(buf, "<")?;
+    print_type_ref(db, self_ty, buf)?;
+    write!(buf, " as ")?;
+    print_path_with_opts(db, &trait_path.unwrap(), buf, opts)?;
+    write!(buf, ">")?;
+    for segment in segments.skip(idx + 1).iter() {
+        write!(buf, "::")?;
+        print_path_segment(db, segment, buf, opts)?;
     }
+    Ok(())
+}
 
+fn print_path_segment(
+    db: &dyn DefDatabase,
+    segment: PathSegment<'_>,
+    buf: &mut dyn Write,
+    opts: PrettyPrintOpts,
+) -> fmt::Result {
+    write!(buf, "{}", segment.name.display(db.upcast()))?;
+    if let Some(generics) = segment.args_and_bindings.filter(|_| opts.show_generic_args) {
+        if generics.is_turbofish {
+            write!(buf, "::")?;
+        }
+        write!(buf, "<")?;
+        print_generic_args(db, generics, buf)?;
+        write!(buf, ">")?;
+    }
     Ok(())
 }
 
@@ -96,16 +179,12 @@ pub(crate) fn print_generic_args(
     buf: &mut dyn Write,
 ) -> fmt::Result {
     let mut first = true;
-    let args = if generics.has_self_type {
-        let (self_ty, args) = generics.args.split_first().unwrap();
+    if let Some(self_ty) = &generics.self_type {
         write!(buf, "Self=")?;
-        print_generic_arg(db, self_ty, buf)?;
+        print_type_ref(db, self_ty, buf)?;
         first = false;
-        args
-    } else {
-        &generics.args
-    };
-    for arg in args {
+    }
+    for arg in generics.args.iter() {
         if !first {
             write!(buf, ", ")?;
         }
@@ -168,7 +247,7 @@ pub(crate) fn print_type_ref(
                 Mutability::Mut => "*mut",
             };
             write!(buf, "{mtbl} ")?;
-            print_type_ref(db, pointee, buf)?;
+            print_pointee(db, pointee, buf)?;
         }
         TypeRef::Reference(pointee, lt, mtbl) => {
             let mtbl = match mtbl {
@@ -180,7 +259,7 @@ pub(crate) fn print_type_ref(
                 write!(buf, "{} ", lt.name.display(db.upcast()))?;
             }
             write!(buf, "{mtbl}")?;
-            print_type_ref(db, pointee, buf)?;
+            print_pointee(db, pointee, buf)?;
         }
         TypeRef::Array(elem, len) => {
             write!(buf, "[")?;
@@ -193,15 +272,17 @@ pub(crate) fn print_type_ref(
             write!(buf, "]")?;
         }
         TypeRef::Fn(args_and_ret, varargs, is_unsafe, abi) => {
-            let ((_, return_type), args) =
+            let ((_, return_type), mut args) =
                 args_and_ret.split_last().expect("TypeRef::Fn is missing return type");
+            // The `...` of variadic fns is lowered as a trailing param, print it as `...` instead.
+            if *varargs {
+                args = args.split_last().map_or(args, |(_, it)| it);
+            }
             if *is_unsafe {
                 write!(buf, "unsafe ")?;
             }
             if let Some(abi) = abi {
-                buf.write_str("extern ")?;
-                buf.write_str(abi)?;
-                buf.write_char(' ')?;
+                write!(buf, "extern \"{}\" ", &**abi)?;
             }
             write!(buf, "fn(")?;
             for (i, (_, typeref)) in args.iter().enumerate() {
@@ -236,6 +317,19 @@ pub(crate) fn print_type_ref(
     Ok(())
 }
 
+/// Prints the pointee of a reference or raw pointer, parenthesizing trait objects with several
+/// bounds since `&dyn A + B` would not parse back to the same type.
+fn print_pointee(db: &dyn DefDatabase, pointee: &TypeRef, buf: &mut dyn Write) -> fmt::Result {
+    match pointee {
+        TypeRef::ImplTrait(bounds) | TypeRef::DynTrait(bounds) if bounds.len() > 1 => {
+            write!(buf, "(")?;
+            print_type_ref(db, pointee, buf)?;
+            write!(buf, ")")
+        }
+        _ => print_type_ref(db, pointee, buf),
+    }
+}
+
 pub(crate) fn print_type_bounds(
     db: &dyn DefDatabase,
     bounds: &[Interned<TypeBound>],
@@ -13,8 +13,8 @@
 };
 use base_db::CrateId;
 use smallvec::SmallVec;
-use span::SyntaxContextId;
-use syntax::{ast, AstNode};
+use span::{Span, SyntaxContextId};
+use syntax::{ast, AstNode, SmolStr};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ModPath {
@@ -44,6 +44,26 @@ pub enum PathKind {
     DollarCrate(CrateId),
 }
 
+impl PathKind {
+    /// Writes the leading part of a path of this kind, including the trailing `::` (e.g.
+    /// `super::super::` for `Super(2)`). Writes nothing for `Plain`.
+    pub fn fmt_leading(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        match self {
+            PathKind::Plain => Ok(()),
+            PathKind::Super(0) => f.write_str("self::"),
+            PathKind::Super(n) => (0..*n).try_for_each(|_| f.write_str("super::")),
+            PathKind::Crate => f.write_str("crate::"),
+            PathKind::Abs => f.write_str("::"),
+            PathKind::DollarCrate(_) => f.write_str("$crate::"),
+        }
+    }
+
+    /// Returns `true` if paths of this kind start with a bare `::` separator, i.e. `::foo`.
+    pub fn has_leading_separator(&self) -> bool {
+        matches!(self, PathKind::Abs)
+    }
+}
+
 impl ModPath {
     pub fn from_src(
         db: &dyn ExpandDatabase,
@@ -63,6 +83,14 @@ pub fn from_segments(kind: PathKind, segments: impl IntoIterator<Item = Name>) -
         ModPath { kind, segments }
     }
 
+    /// Creates an absolute path into the crate `crate_name`, e.g. `::std::collections::HashMap`.
+    pub fn from_fully_qualified(
+        crate_name: &Name,
+        segments: impl IntoIterator<Item = Name>,
+    ) -> ModPath {
+        ModPath::from_segments(PathKind::Abs, iter::once(crate_name.clone()).chain(segments))
+    }
+
     /// Creates a `ModPath` from a `PathKind`, with no extra path segments.
     pub const fn from_kind(kind: PathKind) -> ModPath {
         ModPath { kind, segments: SmallVec::new_const() }
@@ -141,6 +169,39 @@ pub fn unescaped(&self) -> UnescapedModPath<'_> {
     pub fn display<'a>(&'a self, db: &'a dyn crate::db::ExpandDatabase) -> impl fmt::Display + 'a {
         Display { db, path: self }
     }
+
+    /// Returns the tokens of this path, all with the given `span`, for splicing it into macro
+    /// input or output, like the trait path in the expansion of a builtin derive.
+    pub fn to_token_tree(&self, span: Span) -> tt::Subtree {
+        let mut idents: Vec<SmolStr> = match self.kind {
+            PathKind::Plain | PathKind::Abs => Vec::new(),
+            PathKind::Super(0) => vec![SmolStr::new_static("self")],
+            PathKind::Super(n) => vec![SmolStr::new_static("super"); n as usize],
+            PathKind::Crate => vec![SmolStr::new_static("crate")],
+            PathKind::DollarCrate(_) => vec![SmolStr::new_static("$crate")],
+        };
+        idents.extend(self.segments.iter().map(Name::to_smol_str));
+
+        let mut token_trees = Vec::new();
+        let push_separator = |token_trees: &mut Vec<tt::TokenTree>| {
+            for spacing in [tt::Spacing::Joint, tt::Spacing::Alone] {
+                token_trees.push(tt::Leaf::Punct(tt::Punct { char: ':', spacing, span }).into());
+            }
+        };
+        if self.kind == PathKind::Abs {
+            push_separator(&mut token_trees);
+        }
+        for (i, text) in idents.into_iter().enumerate() {
+            if i != 0 {
+                push_separator(&mut token_trees);
+            }
+            token_trees.push(tt::Leaf::Ident(tt::Ident { text, span }).into());
+        }
+        tt::Subtree {
+            delimiter: tt::Delimiter::invisible_spanned(span),
+            token_trees: token_trees.into_boxed_slice(),
+        }
+    }
 }
 
 impl Extend<Name> for ModPath {
@@ -373,6 +434,13 @@ macro_rules! __known_path {
     (core::iter::Iterator) => {};
     (core::result::Result) => {};
     (core::option::Option) => {};
+    (core::option::Option::Some) => {};
+    (core::option::Option::None) => {};
+    (std::option::Option) => {};
+    (std::option::Option::Some) => {};
+    (std::option::Option::None) => {};
+    (std::vec::Vec) => {};
+    (alloc::vec::Vec) => {};
     (core::ops::Range) => {};
     (core::ops::RangeFrom) => {};
     (core::ops::RangeFull) => {};
@@ -382,6 +450,25 @@ macro_rules! __known_path {
     (core::future::Future) => {};
     (core::future::IntoFuture) => {};
     (core::ops::Try) => {};
+    (core::ops::Index) => {};
+    (core::ops::IndexMut) => {};
+    (core::ops::Deref) => {};
+    (core::ops::Neg) => {};
+    (core::ops::Not) => {};
+    (core::convert::From) => {};
+    (core::convert::Into) => {};
+    (core::convert::TryFrom) => {};
+    (core::default::Default) => {};
+    (std::iter::Iterator) => {};
+    (std::ops::Index) => {};
+    (std::ops::IndexMut) => {};
+    (std::ops::Deref) => {};
+    (std::ops::Neg) => {};
+    (std::ops::Not) => {};
+    (std::convert::From) => {};
+    (std::convert::Into) => {};
+    (std::convert::TryFrom) => {};
+    (std::default::Default) => {};
     ($path:path) => {
         compile_error!("Please register your known path in the path module")
     };
@@ -391,10 +478,133 @@ macro_rules! __known_path {
 macro_rules! __path {
     ($start:ident $(:: $seg:ident)*) => ({
         $crate::__known_path!($start $(:: $seg)*);
-        $crate::mod_path::ModPath::from_segments($crate::mod_path::PathKind::Abs, vec![
-            $crate::mod_path::__name![$start], $($crate::mod_path::__name![$seg],)*
+        $crate::mod_path::ModPath::from_fully_qualified(&$crate::mod_path::__name![$start], vec![
+            $($crate::mod_path::__name![$seg],)*
         ])
     });
 }
 
 pub use crate::__path as path;
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+
+    use base_db::FileId;
+    use expect_test::{expect, Expect};
+    use la_arena::RawIdx;
+    use span::{Span, SpanAnchor, SyntaxContextId, ROOT_ERASED_FILE_AST_ID};
+    use syntax::{TextRange, TextSize};
+
+    use super::{path, CrateId, ModPath, PathKind};
+    use crate::name::name;
+
+    struct Leading(PathKind);
+
+    impl fmt::Display for Leading {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt_leading(f)
+        }
+    }
+
+    fn dollar_crate() -> PathKind {
+        PathKind::DollarCrate(CrateId::from_raw(RawIdx::from(0)))
+    }
+
+    #[test]
+    fn fmt_leading() {
+        assert_eq!(format!("{}", Leading(PathKind::Plain)), "");
+        assert_eq!(format!("{}", Leading(PathKind::Super(0))), "self::");
+        assert_eq!(format!("{}", Leading(PathKind::Super(2))), "super::super::");
+        assert_eq!(format!("{}", Leading(PathKind::Crate)), "crate::");
+        assert_eq!(format!("{}", Leading(PathKind::Abs)), "::");
+        assert_eq!(format!("{}", Leading(dollar_crate())), "$crate::");
+    }
+
+    #[test]
+    fn known_paths() {
+        let vec = path![std::vec::Vec];
+        assert_eq!(vec.kind, PathKind::Abs);
+        assert_eq!(vec.segments(), [name![std], name![vec], name![Vec]]);
+        assert_eq!(path![alloc::vec::Vec].segments(), [name![alloc], name![vec], name![Vec]]);
+        assert_eq!(
+            path![std::option::Option].segments(),
+            [name![std], name![option], name![Option]]
+        );
+        assert_eq!(
+            path![core::option::Option].segments(),
+            [name![core], name![option], name![Option]]
+        );
+
+        let some = path![std::option::Option::Some];
+        assert_eq!(some.kind, PathKind::Abs);
+        assert_eq!(some.segments(), [name![std], name![option], name![Option], name![Some]]);
+        assert_eq!(path![std::option::Option::None].segments().last(), Some(&name![None]));
+        assert_eq!(
+            path![core::option::Option::Some].segments(),
+            [name![core], name![option], name![Option], name![Some]]
+        );
+        assert_eq!(path![core::option::Option::None].segments().last(), Some(&name![None]));
+    }
+
+    #[test]
+    fn known_trait_paths() {
+        assert_eq!(
+            path![core::convert::TryFrom].segments(),
+            [name![core], name![convert], name![TryFrom]]
+        );
+        assert_eq!(
+            path![std::default::Default].segments(),
+            [name![std], name![default], name![Default]]
+        );
+        assert_eq!(path![std::iter::Iterator].segments().last(), Some(&name![Iterator]));
+        for (path, name) in [
+            (path![core::ops::Index], name![Index]),
+            (path![core::ops::IndexMut], name![IndexMut]),
+            (path![core::ops::Deref], name![Deref]),
+            (path![std::ops::Neg], name![Neg]),
+            (path![std::ops::Not], name![Not]),
+            (path![std::convert::From], name![From]),
+            (path![std::convert::Into], name![Into]),
+        ] {
+            assert_eq!(path.kind, PathKind::Abs);
+            assert_eq!(path.segments().len(), 3);
+            assert_eq!(path.segments().last(), Some(&name));
+        }
+    }
+
+    #[test]
+    fn to_token_tree() {
+        const DUMMY: Span = Span {
+            range: TextRange::empty(TextSize::new(0)),
+            anchor: SpanAnchor { file_id: FileId::BOGUS, ast_id: ROOT_ERASED_FILE_AST_ID },
+            ctx: SyntaxContextId::ROOT,
+        };
+        let check = |path: ModPath, expect: Expect| {
+            let tt = path.to_token_tree(DUMMY);
+            expect.assert_eq(&tt.to_string());
+            assert!(tt.token_trees.iter().all(|it| it.first_span() == DUMMY));
+        };
+
+        check(path![core::convert::From], expect![[r#":: core :: convert :: From"#]]);
+        check(
+            ModPath::from_segments(dollar_crate(), [name![convert], name![From]]),
+            expect![[r#"$crate :: convert :: From"#]],
+        );
+        check(
+            ModPath::from_segments(PathKind::Super(2), [name![From]]),
+            expect![[r#"super :: super :: From"#]],
+        );
+        check(ModPath::from_kind(PathKind::Super(0)), expect![[r#"self"#]]);
+        check(ModPath::from(name![From]), expect![[r#"From"#]]);
+    }
+
+    #[test]
+    fn has_leading_separator() {
+        assert!(PathKind::Abs.has_leading_separator());
+        assert!(!PathKind::Plain.has_leading_separator());
+        assert!(!PathKind::Crate.has_leading_separator());
+        assert!(!PathKind::Super(1).has_leading_separator());
+        assert!(!dollar_crate().has_leading_separator());
+    }
+}
@@ -250,6 +250,12 @@ fn as_name(&self) -> Name {
     }
 }
 
+impl AsName for base_db::CrateName {
+    fn as_name(&self) -> Name {
+        Name::new_text(self.as_smol_str().clone())
+    }
+}
+
 pub mod known {
     macro_rules! known_names {
         ($($ident:ident),* $(,)?) => {
@@ -299,10 +305,14 @@ macro_rules! known_names {
         string,
         boxed,
         option,
+        vec,
+        convert,
+        default,
         prelude,
         rust_2015,
         rust_2018,
         rust_2021,
+        rust_2024,
         v1,
         new_display,
         new_debug,
@@ -329,6 +339,8 @@ macro_rules! known_names {
         IntoFuture,
         Result,
         Option,
+        Some,
+        Vec,
         Output,
         Target,
         Box,
@@ -343,6 +355,11 @@ macro_rules! known_names {
         Not,
         None,
         Index,
+        IndexMut,
+        Deref,
+        From,
+        Into,
+        TryFrom,
         Left,
         Right,
         Center,
@@ -1909,17 +1909,15 @@ fn hir_fmt(&self, f: &mut HirFormatter<'_>) -> Result<(), HirDisplayError> {
         }
 
         // Convert trait's `Self` bound back to the surface syntax. Note there is no associated
-        // trait, so there can only be one path segment that `has_self_type`. The `Self` type
+        // trait, so there can only be one path segment that has a `self_type`. The `Self` type
         // itself can contain further qualified path through, which will be handled by recursive
         // `hir_fmt`s.
         //
         // `trait_mod::Trait<Self = type_mod::Type, Args>::Assoc`
         // =>
         // `<type_mod::Type as trait_mod::Trait<Args>>::Assoc`
-        let trait_self_ty = self.segments().iter().find_map(|seg| {
-            let generic_args = seg.args_and_bindings?;
-            generic_args.has_self_type.then(|| &generic_args.args[0])
-        });
+        let trait_self_ty =
+            self.segments().iter().find_map(|seg| seg.args_and_bindings?.self_type.as_ref());
         if let Some(ty) = trait_self_ty {
             write!(f, "<")?;
             ty.hir_fmt(f)?;
@@ -1959,8 +1957,8 @@ fn hir_fmt(&self, f: &mut HirFormatter<'_>) -> Result<(), HirDisplayError> {
                 }
 
                 let mut first = true;
-                // Skip the `Self` bound if exists. It's handled outside the loop.
-                for arg in &generic_args.args[generic_args.has_self_type as usize..] {
+                // The `Self` bound, if it exists, is handled outside the loop.
+                for arg in generic_args.args.iter() {
                     if first {
                         first = false;
                         write!(f, "<")?;
@@ -1996,7 +1994,7 @@ fn hir_fmt(&self, f: &mut HirFormatter<'_>) -> Result<(), HirDisplayError> {
                 }
 
                 // Current position: `<Ty as Trait<Args>|`
-                if generic_args.has_self_type {
+                if generic_args.self_type.is_some() {
                     write!(f, ">")?;
                 }
             }
@@ -861,21 +861,24 @@ fn substs_from_args_and_bindings(
         let mut had_explicit_args = false;
 
         if let Some(generic_args) = &args_and_bindings {
-            if !generic_args.has_self_type {
-                fill_self_params();
+            match &generic_args.self_type {
+                // The `Self` type is ignored if the item has no `Self` parameter.
+                Some(self_ty) if self_params > 0 => {
+                    if let Some(id) = def_generic_iter.next() {
+                        assert!(matches!(id, GenericParamId::TypeParamId(_)));
+                        had_explicit_args = true;
+                        substs.push(self.lower_ty(self_ty).cast(Interner));
+                    }
+                }
+                Some(_) => {}
+                None => fill_self_params(),
             }
-            let expected_num = if generic_args.has_self_type {
-                self_params + type_params + const_params
-            } else {
-                type_params + const_params
-            };
-            let skip = if generic_args.has_self_type && self_params == 0 { 1 } else { 0 };
+            let expected_num = type_params + const_params;
             // if args are provided, it should be all of them, but we can't rely on that
             for arg in generic_args
                 .args
                 .iter()
                 .filter(|arg| !matches!(arg, GenericArg::Lifetime(_)))
-                .skip(skip)
                 .take(expected_num)
             {
                 if let Some(id) = def_generic_iter.next() {
@@ -3,6 +3,7 @@
 //!
 //! This probably isn't the best way to do this -- ideally, diagnostics should
 //! be expressed in terms of hir types themselves.
+pub use hir_def::item_tree::UseTreeError;
 pub use hir_ty::diagnostics::{CaseType, IncorrectCase};
 use hir_ty::{db::HirDatabase, diagnostics::BodyValidationDiagnostic, InferenceDiagnostic};
 
@@ -57,6 +58,7 @@ fn from(d: $diag) -> AnyDiagnostic {
     MacroError,
     MacroExpansionParseError,
     MalformedDerive,
+    MalformedUseTree,
     MismatchedArgCount,
     MismatchedTupleStructPatArgCount,
     MissingFields,
@@ -122,6 +124,12 @@ pub struct UnresolvedImport {
     pub decl: InFile<AstPtr<ast::UseTree>>,
 }
 
+#[derive(Debug)]
+pub struct MalformedUseTree {
+    pub tree: InFile<AstPtr<ast::UseTree>>,
+    pub error: UseTreeError,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct UnresolvedMacroCall {
     pub macro_call: InFile<SyntaxNodePtr>,
@@ -82,7 +82,7 @@
 use span::Edition;
 use stdx::{impl_from, never};
 use syntax::{
-    ast::{self, HasAttrs as _, HasName},
+    ast::{self, HasAttrs as _, HasModuleItem as _, HasName},
     format_smolstr, AstNode, AstPtr, SmolStr, SyntaxNode, SyntaxNodePtr, TextRange, T,
 };
 use triomphe::Arc;
@@ -558,6 +558,7 @@ pub fn diagnostics(
             }
             emit_def_diagnostic(db, acc, diag);
         }
+        emit_malformed_use_trees(db, acc, self);
 
         for def in self.declarations(db) {
             match def {
@@ -826,6 +827,27 @@ fn emit_macro_def_diagnostics(db: &dyn HirDatabase, acc: &mut Vec<AnyDiagnostic>
     }
 }
 
+/// Reports the use trees of `module`'s own `use` items that were dropped during lowering.
+fn emit_malformed_use_trees(db: &dyn HirDatabase, acc: &mut Vec<AnyDiagnostic>, module: Module) {
+    let InFile { file_id, value: source } = module.definition_source(db);
+    let items: Vec<ast::Item> = match source {
+        ModuleSource::SourceFile(it) => it.items().collect(),
+        ModuleSource::Module(it) => it.item_list().into_iter().flat_map(|it| it.items()).collect(),
+        ModuleSource::BlockExpr(it) => {
+            it.stmt_list().into_iter().flat_map(|it| it.items()).collect()
+        }
+    };
+    for item in items {
+        let ast::Item::Use(use_item) = item else { continue };
+        let Some(use_tree) = use_item.use_tree() else { continue };
+        for (tree, error) in hir_def::item_tree::use_tree_errors(db.upcast(), file_id, use_tree) {
+            acc.push(
+                MalformedUseTree { tree: InFile::new(file_id, AstPtr::new(&tree)), error }.into(),
+            );
+        }
+    }
+}
+
 fn emit_def_diagnostic(db: &dyn HirDatabase, acc: &mut Vec<AnyDiagnostic>, diag: &DefDiagnostic) {
     emit_def_diagnostic_(db, acc, &diag.kind)
 }
@@ -0,0 +1,40 @@
+use hir::UseTreeError;
+
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+// Diagnostic: malformed-use-tree
+//
+// This diagnostic is triggered if a use tree can't be imported, like the top-level `self` in
+// `use self;`.
+pub(crate) fn malformed_use_tree(
+    ctx: &DiagnosticsContext<'_>,
+    d: &hir::MalformedUseTree,
+) -> Diagnostic {
+    let malformed = DiagnosticCode::Ra("malformed-use-tree", Severity::Error);
+    let (code, message) = match d.error {
+        UseTreeError::InvalidPath => (malformed, "invalid path in use tree"),
+        UseTreeError::MissingPath => (malformed, "use tree is missing a path"),
+        UseTreeError::RenamedGlob => (malformed, "glob imports can't be renamed"),
+        UseTreeError::SelfImport => (
+            DiagnosticCode::RustcHardError("E0429"),
+            "`self` imports are only allowed within a { } list",
+        ),
+    };
+    Diagnostic::new_with_syntax_node_ptr(ctx, code, message, d.tree.map(|it| it.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::check_diagnostics_with_disabled;
+
+    #[test]
+    fn self_import() {
+        check_diagnostics_with_disabled(
+            r#"
+use self;
+  //^^^^ error: `self` imports are only allowed within a { } list
+"#,
+            &["E0432"],
+        );
+    }
+}
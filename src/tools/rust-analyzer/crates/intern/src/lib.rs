@@ -3,6 +3,7 @@
 //! Eventually this should probably be replaced with salsa-based interning.
 
 use std::{
+    cmp::Ordering,
     fmt::{self, Debug, Display},
     hash::{BuildHasherDefault, Hash, Hasher},
     ops::Deref,
@@ -129,6 +130,40 @@ fn eq(&self, other: &Self) -> bool {
 
 impl Eq for Interned<str> {}
 
+/// Orders interned `Ref`s by their values, so that the order doesn't depend on where the values
+/// happen to be allocated.
+impl<T: Internable + Ord> PartialOrd for Interned<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Internable + Ord> Ord for Interned<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self == other {
+            Ordering::Equal
+        } else {
+            (**self).cmp(&**other)
+        }
+    }
+}
+
+impl PartialOrd for Interned<str> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interned<str> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self == other {
+            Ordering::Equal
+        } else {
+            (**self).cmp(&**other)
+        }
+    }
+}
+
 impl<T: Internable + ?Sized> Hash for Interned<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         // NOTE: Cast disposes vtable pointer / slice/str length.
@@ -7,6 +7,7 @@
 
 use std::{
     any::type_name,
+    cmp::Ordering,
     fmt,
     hash::{BuildHasher, BuildHasherDefault, Hash, Hasher},
     marker::PhantomData,
@@ -39,6 +40,16 @@ fn eq(&self, other: &Self) -> bool {
     }
 }
 impl<N: AstIdNode> Eq for FileAstId<N> {}
+impl<N: AstIdNode> PartialOrd for FileAstId<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<N: AstIdNode> Ord for FileAstId<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.raw.cmp(&other.raw)
+    }
+}
 impl<N: AstIdNode> Hash for FileAstId<N> {
     fn hash<H: Hasher>(&self, hasher: &mut H) {
         self.raw.hash(hasher);